@@ -0,0 +1,334 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::error::{ApduError, TlvError};
+use crate::iso14443_4::IsoDepPayload;
+use crate::tlv::Tlv;
+
+/// Command APDU, as defined in ISO 7816-4.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommandApdu {
+    pub cla: u8,
+    pub ins: u8,
+    pub p1: u8,
+    pub p2: u8,
+    pub data: Vec<u8>,
+    /// Expected response length; encoded 0 is given as 256, or 65536 for
+    /// extended length.
+    pub le: Option<u32>,
+    pub extended: bool,
+}
+
+impl CommandApdu {
+    pub fn from_bytes(apdu: &[u8]) -> Result<Self, ApduError> {
+        let [cla, ins, p1, p2, body @ ..] = apdu else {
+            return Err(ApduError::TooShort);
+        };
+        let mut command_apdu = CommandApdu {
+            cla: *cla,
+            ins: *ins,
+            p1: *p1,
+            p2: *p2,
+            data: Vec::new(),
+            le: None,
+            extended: false,
+        };
+        match body {
+            // case 1
+            [] => {}
+            // case 2 short
+            [le] => command_apdu.le = Some(short_le(*le)),
+            // extended length
+            [0, rest @ ..] if rest.len() >= 2 => {
+                command_apdu.extended = true;
+                let length = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+                let rest = &rest[2..];
+                match rest.len() {
+                    // case 2 extended
+                    0 => command_apdu.le = Some(extended_le([0, 0], length)),
+                    // case 3 extended
+                    n if (length != 0) & (n == length) => command_apdu.data = rest.to_vec(),
+                    // case 4 extended
+                    n if (length != 0) & (n == length + 2) => {
+                        command_apdu.data = rest[..length].to_vec();
+                        command_apdu.le = Some(extended_le([rest[length], rest[length + 1]], 0));
+                    }
+                    _ => return Err(ApduError::WrongLength),
+                }
+            }
+            [0, _] => return Err(ApduError::WrongLength),
+            [lc, rest @ ..] => {
+                let length = *lc as usize;
+                match rest.len() {
+                    // case 3 short
+                    n if n == length => command_apdu.data = rest.to_vec(),
+                    // case 4 short
+                    n if n == length + 1 => {
+                        command_apdu.data = rest[..length].to_vec();
+                        command_apdu.le = Some(short_le(rest[length]));
+                    }
+                    _ => return Err(ApduError::WrongLength),
+                }
+            }
+        }
+        Ok(command_apdu)
+    }
+}
+
+fn short_le(le: u8) -> u32 {
+    if le == 0 {
+        256
+    } else {
+        le as u32
+    }
+}
+
+/// Extended Le from two bytes, or from `length` for case 2 extended.
+fn extended_le(le: [u8; 2], length: usize) -> u32 {
+    match u16::from_be_bytes(le) as u32 + length as u32 {
+        0 => 65536,
+        le => le,
+    }
+}
+
+/// Response APDU, as defined in ISO 7816-4.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResponseApdu {
+    pub data: Vec<u8>,
+    pub sw1: u8,
+    pub sw2: u8,
+}
+
+impl ResponseApdu {
+    pub fn from_bytes(apdu: &[u8]) -> Result<Self, ApduError> {
+        match apdu {
+            [data @ .., sw1, sw2] => Ok(ResponseApdu {
+                data: data.to_vec(),
+                sw1: *sw1,
+                sw2: *sw2,
+            }),
+            _ => Err(ApduError::TooShort),
+        }
+    }
+
+    pub fn sw(&self) -> u16 {
+        u16::from_be_bytes([self.sw1, self.sw2])
+    }
+
+    /// Normal processing, 9000 or 61XX.
+    pub fn is_success(&self) -> bool {
+        (self.sw() == 0x9000) | (self.sw1 == 0x61)
+    }
+
+    /// Warning processing, 62XX or 63XX.
+    pub fn is_warning(&self) -> bool {
+        (self.sw1 == 0x62) | (self.sw1 == 0x63)
+    }
+
+    pub fn status(&self) -> &'static str {
+        status_meaning(self.sw1, self.sw2)
+    }
+
+    /// Response data as BER-TLV data objects.
+    pub fn tlv(&self) -> Result<Vec<Tlv>, TlvError> {
+        Tlv::parse_all(&self.data)
+    }
+}
+
+/// Meaning of status word, as listed in ISO 7816-4.
+pub fn status_meaning(sw1: u8, sw2: u8) -> &'static str {
+    match (sw1, sw2) {
+        (0x90, 0x00) => "no further qualification",
+        (0x61, _) => "SW2 encodes the number of data bytes still available",
+        (0x62, 0x81) => "part of returned data may be corrupted",
+        (0x62, 0x82) => "end of file or record reached before reading Ne bytes",
+        (0x62, 0x83) => "selected file deactivated",
+        (0x62, 0x84) => "file control information not formatted",
+        (0x62, _) => "state of non-volatile memory is unchanged",
+        (0x63, 0x81) => "file filled up by the last write",
+        (0x63, sw2) if sw2 & 0xF0 == 0xC0 => "counter in low nibble of SW2, e.g. retries left",
+        (0x63, _) => "state of non-volatile memory has changed",
+        (0x64, _) => "execution error, state of non-volatile memory is unchanged",
+        (0x65, 0x81) => "memory failure",
+        (0x65, _) => "execution error, state of non-volatile memory has changed",
+        (0x66, _) => "security-related issue",
+        (0x67, 0x00) => "wrong length",
+        (0x68, 0x81) => "logical channel not supported",
+        (0x68, 0x82) => "secure messaging not supported",
+        (0x68, 0x83) => "last command of the chain expected",
+        (0x68, 0x84) => "command chaining not supported",
+        (0x68, _) => "functions in CLA not supported",
+        (0x69, 0x81) => "command incompatible with file structure",
+        (0x69, 0x82) => "security status not satisfied",
+        (0x69, 0x83) => "authentication method blocked",
+        (0x69, 0x84) => "reference data not usable",
+        (0x69, 0x85) => "conditions of use not satisfied",
+        (0x69, 0x86) => "command not allowed, no current EF",
+        (0x69, 0x87) => "expected secure messaging data objects missing",
+        (0x69, 0x88) => "incorrect secure messaging data objects",
+        (0x69, _) => "command not allowed",
+        (0x6A, 0x80) => "incorrect parameters in the command data field",
+        (0x6A, 0x81) => "function not supported",
+        (0x6A, 0x82) => "file or application not found",
+        (0x6A, 0x83) => "record not found",
+        (0x6A, 0x84) => "not enough memory space in the file",
+        (0x6A, 0x86) => "incorrect parameters P1-P2",
+        (0x6A, 0x88) => "referenced data or reference data not found",
+        (0x6A, _) => "wrong parameters P1-P2",
+        (0x6B, 0x00) => "wrong parameters P1-P2",
+        (0x6C, _) => "wrong Le field, SW2 encodes the exact number of available data bytes",
+        (0x6D, 0x00) => "instruction code not supported or invalid",
+        (0x6E, 0x00) => "class not supported",
+        (0x6F, 0x00) => "no precise diagnosis",
+        _ => "unknown status",
+    }
+}
+
+/// APDU carried in ISO-DEP payload.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Apdu {
+    Command(CommandApdu),
+    Response(ResponseApdu),
+}
+
+impl Apdu {
+    pub fn from_payload(payload: &IsoDepPayload) -> Result<Self, ApduError> {
+        match payload {
+            IsoDepPayload::Command(data) => CommandApdu::from_bytes(data).map(Apdu::Command),
+            IsoDepPayload::Response(data) => ResponseApdu::from_bytes(data).map(Apdu::Response),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{Frame, FrameAttributed};
+    use crate::iso14443_4::InfAssembler;
+    use std::string::ToString;
+
+    #[test]
+    fn command_short() {
+        assert_eq!(
+            CommandApdu::from_bytes(&[0x00, 0xB0, 0x00, 0x00]),
+            Ok(CommandApdu {
+                cla: 0x00,
+                ins: 0xB0,
+                p1: 0x00,
+                p2: 0x00,
+                data: vec![],
+                le: None,
+                extended: false,
+            })
+        );
+        assert_eq!(
+            CommandApdu::from_bytes(&[0x00, 0xB2, 0x01, 0x0C, 0x00])
+                .unwrap()
+                .le,
+            Some(256)
+        );
+        let select = CommandApdu::from_bytes(&[
+            0x00, 0xA4, 0x04, 0x00, 0x0E, 0x32, 0x50, 0x41, 0x59, 0x2E, 0x53, 0x59, 0x53, 0x2E,
+            0x44, 0x44, 0x46, 0x30, 0x31, 0x00,
+        ])
+        .unwrap();
+        assert_eq!(select.data, b"2PAY.SYS.DDF01".to_vec());
+        assert_eq!(select.le, Some(256));
+        assert_eq!(
+            CommandApdu::from_bytes(&[0x00, 0xD6, 0x00, 0x00, 0x02, 0x01, 0x02])
+                .unwrap()
+                .data,
+            vec![0x01, 0x02]
+        );
+    }
+
+    #[test]
+    fn command_extended() {
+        let apdu = CommandApdu::from_bytes(&[0x00, 0xB0, 0x00, 0x00, 0x00, 0x01, 0x00]).unwrap();
+        assert!(apdu.extended);
+        assert_eq!(apdu.le, Some(256));
+        let apdu = CommandApdu::from_bytes(&[0x00, 0xB0, 0x00, 0x00, 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(apdu.le, Some(65536));
+        let apdu = CommandApdu::from_bytes(&[
+            0x00, 0xD6, 0x00, 0x00, 0x00, 0x00, 0x02, 0xAA, 0xBB, 0x00, 0x10,
+        ])
+        .unwrap();
+        assert_eq!(apdu.data, vec![0xAA, 0xBB]);
+        assert_eq!(apdu.le, Some(16));
+        let apdu = CommandApdu::from_bytes(&[0x00, 0xD6, 0x00, 0x00, 0x00, 0x00, 0x02, 0xAA, 0xBB])
+            .unwrap();
+        assert_eq!(apdu.le, None);
+    }
+
+    #[test]
+    fn command_broken() {
+        assert_eq!(
+            CommandApdu::from_bytes(&[0x00, 0xA4, 0x04]),
+            Err(ApduError::TooShort)
+        );
+        assert_eq!(
+            CommandApdu::from_bytes(&[0x00, 0xA4, 0x04, 0x00, 0x05, 0x01]),
+            Err(ApduError::WrongLength)
+        );
+    }
+
+    #[test]
+    fn response() {
+        let apdu = ResponseApdu::from_bytes(&[0x6F, 0x00, 0x90, 0x00]).unwrap();
+        assert_eq!(apdu.data, vec![0x6F, 0x00]);
+        assert_eq!(apdu.sw(), 0x9000);
+        assert!(apdu.is_success());
+        let apdu = ResponseApdu::from_bytes(&[0x6A, 0x82]).unwrap();
+        assert!(!apdu.is_success());
+        assert_eq!(apdu.status(), "file or application not found");
+        assert_eq!(
+            status_meaning(0x63, 0xC2),
+            "counter in low nibble of SW2, e.g. retries left"
+        );
+        assert_eq!(ResponseApdu::from_bytes(&[0x90]), Err(ApduError::TooShort));
+    }
+
+    #[test]
+    fn select_ppse_frames() {
+        let mut select_ppse = vec![0x02, 0x00, 0xA4, 0x04, 0x00, 0x0E];
+        select_ppse.extend_from_slice(b"2PAY.SYS.DDF01");
+        select_ppse.push(0x00);
+        let session = [
+            FrameAttributed::Miller(Frame::Standard(select_ppse)),
+            FrameAttributed::Manchester(Frame::Standard(vec![
+                0x02, 0x6F, 0x10, 0x84, 0x0E, 0x32, 0x50, 0x41, 0x59, 0x2E, 0x53, 0x59, 0x53, 0x2E,
+                0x44, 0x44, 0x46, 0x30, 0x31, 0x90, 0x00,
+            ])),
+        ];
+        let mut inf_assembler = InfAssembler::new();
+        let apdus: Vec<Apdu> = session
+            .iter()
+            .filter_map(|frame| inf_assembler.add_frame(frame))
+            .map(|payload| Apdu::from_payload(&payload).unwrap())
+            .collect();
+        let Apdu::Response(response) = &apdus[1] else {
+            panic!("second APDU is a response")
+        };
+        assert_eq!(
+            response.tlv().unwrap()[0].to_string(),
+            "6F File Control Information (FCI) Template
+  84 Dedicated File (DF) Name: 325041592E5359532E4444463031
+"
+        );
+    }
+
+    #[test]
+    fn payload() {
+        assert_eq!(
+            Apdu::from_payload(&IsoDepPayload::Response(vec![0x90, 0x00])),
+            Ok(Apdu::Response(ResponseApdu {
+                data: vec![],
+                sw1: 0x90,
+                sw2: 0x00,
+            }))
+        );
+    }
+}
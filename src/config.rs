@@ -0,0 +1,216 @@
+use crate::miller_reworked::{MILLER_PAUSE_MAX_TICKS, MILLER_PAUSE_MIN_TICKS};
+
+/// Timing parameters used in decoding.
+///
+/// All lengths except `tick_len` and `tick_len_divisor` are in ticks; tick is
+/// `tick_len / tick_len_divisor` timer counts long. Single bit is 8 ticks long
+/// at any bit rate.
+///
+/// Frames are split at idle gaps; end of frame rules are set with
+/// `accept_trailing_x`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DecoderConfig {
+    /// Tick length, in timer counts, multiplied by `tick_len_divisor`.
+    pub tick_len: u16,
+
+    /// Divisor of `tick_len`, 1 at 106 kbit/s.
+    ///
+    /// Keeps tick length exact at higher bit rates, where tick is shorter
+    /// than the timer resolution allows to express in whole counts, see
+    /// [`BitRate::config`](crate::iso14443_4::BitRate::config).
+    pub tick_len_divisor: u16,
+
+    /// Acceptable deviation of a time interval from its nominal length.
+    pub tolerance: u16,
+
+    /// Intervals in times-down record above this length are idle gaps.
+    ///
+    /// Frame ends at idle gap. No signal corresponds to 2 or more completely
+    /// "on" bits, i.e. 16 ticks, so the shortest gap between pauses of
+    /// different frames is 16+4 = 20 ticks, XYY (20 ticks) or ZYY (24 ticks).
+    pub idle_gap_down: u16,
+
+    /// Intervals in both-edge record above this length are idle gaps.
+    ///
+    /// Frame ends at idle gap. Longest in-frame interval in both-edge record
+    /// is 12 ticks (Miller pause and gap within XZ, or Manchester F).
+    pub idle_gap_both: u16,
+
+    /// Intervals in both-edge record above this length are idle gaps in
+    /// collision-aware Manchester decoding.
+    ///
+    /// Each collided bit is modulated for the whole bit period, so runs of
+    /// collided bits are longer than `idle_gap_both`. Frames are separated by
+    /// at least 1172/fc, i.e. over 73 ticks; default 64 ticks accepts up to
+    /// 7 consecutive collided bits.
+    pub idle_gap_collision: u16,
+
+    /// Shortest Miller pause in both-edge record.
+    pub pause_min: u16,
+
+    /// Longest Miller pause in both-edge record.
+    pub pause_max: u16,
+
+    /// End of frame rule in both-edge Miller decoding: accept frame with the
+    /// last pause in the middle of bit period (X).
+    ///
+    /// Such frame ends with logic 1, followed by logic 0 and end of frame
+    /// with no pauses (YY), as assumed in times-down decoding. If not set,
+    /// the frame ends with XY, which is not a valid end of frame, and is
+    /// rejected as incomplete.
+    pub accept_trailing_x: bool,
+}
+
+impl DecoderConfig {
+    /// Default tolerances and thresholds for given tick length.
+    pub const fn new(tick_len: u16) -> Self {
+        Self {
+            tick_len,
+            tick_len_divisor: 1,
+            tolerance: 1,
+            idle_gap_down: 19,
+            idle_gap_both: 15,
+            idle_gap_collision: 64,
+            pause_min: MILLER_PAUSE_MIN_TICKS,
+            pause_max: MILLER_PAUSE_MAX_TICKS,
+            accept_trailing_x: false,
+        }
+    }
+
+    /// Ticks in timer counts, multiplied by `tick_len_divisor`.
+    fn counts(&self, ticks: u16) -> u32 {
+        ticks as u32 * self.tick_len as u32
+    }
+
+    /// Interval in timer counts, multiplied by `tick_len_divisor`.
+    fn scaled(&self, interval: u16) -> u32 {
+        interval as u32 * self.tick_len_divisor.max(1) as u32
+    }
+
+    /// Ticks in timer counts, rounded.
+    pub fn ticks_to_counts(&self, ticks: u64) -> u64 {
+        let divisor = self.tick_len_divisor.max(1) as u64;
+        (ticks * self.tick_len as u64 + divisor / 2) / divisor
+    }
+
+    /// Interval matches nominal length of `ticks` within the tolerance.
+    pub fn matches(&self, interval: u16, ticks: u16) -> bool {
+        let interval = self.scaled(interval);
+        (interval >= self.counts(ticks.saturating_sub(self.tolerance)))
+            & (interval <= self.counts(ticks.saturating_add(self.tolerance)))
+    }
+
+    /// Interval in times-down record separates frames.
+    pub fn is_idle_down(&self, interval: u16) -> bool {
+        self.scaled(interval) > self.counts(self.idle_gap_down)
+    }
+
+    /// Interval in both-edge record separates frames.
+    pub fn is_idle_both(&self, interval: u16) -> bool {
+        self.scaled(interval) > self.counts(self.idle_gap_both)
+    }
+
+    /// Interval in both-edge record separates frames, in collision-aware
+    /// decoding.
+    pub fn is_idle_collision(&self, interval: u16) -> bool {
+        self.scaled(interval) > self.counts(self.idle_gap_collision)
+    }
+
+    /// Number of half-bit periods (4 ticks) in interval, if it matches any.
+    pub fn half_bits(&self, interval: u16) -> Option<u16> {
+        let half_bit = self.counts(4);
+        let halves = (self.scaled(interval) + half_bit / 2).checked_div(half_bit)? as u16;
+        if (halves != 0) & self.matches(interval, halves.saturating_mul(4)) {
+            Some(halves)
+        } else {
+            None
+        }
+    }
+
+    /// Interval is acceptable as Miller pause length.
+    pub fn is_pause(&self, interval: u16) -> bool {
+        let interval = self.scaled(interval);
+        (interval >= self.counts(self.pause_min)) & (interval <= self.counts(self.pause_max))
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_default() {
+        let config = DecoderConfig::new(22);
+        assert!(!config.matches(153, 8));
+        assert!(config.matches(154, 8));
+        assert!(config.matches(198, 8));
+        assert!(!config.matches(199, 8));
+        assert!(!config.is_idle_down(418));
+        assert!(config.is_idle_down(419));
+        assert!(!config.is_idle_both(330));
+        assert!(config.is_idle_both(331));
+        assert!(!config.is_idle_collision(1408));
+        assert!(config.is_idle_collision(1409));
+    }
+
+    #[test]
+    fn windows_wide_tolerance() {
+        let config = DecoderConfig {
+            tolerance: 2,
+            ..DecoderConfig::new(10)
+        };
+        assert!(config.matches(60, 8));
+        assert!(config.matches(100, 8));
+        assert!(!config.matches(101, 8));
+    }
+
+    #[test]
+    fn half_bits() {
+        let config = DecoderConfig::new(22);
+        assert_eq!(config.half_bits(20), None);
+        assert_eq!(config.half_bits(88), Some(1));
+        assert_eq!(config.half_bits(170), Some(2));
+        assert_eq!(config.half_bits(130), None);
+        assert_eq!(config.half_bits(530), Some(6));
+    }
+
+    #[test]
+    fn fractional_tick() {
+        let config = DecoderConfig {
+            tick_len_divisor: 8,
+            ..DecoderConfig::new(22)
+        };
+        assert_eq!(config.ticks_to_counts(8), 22);
+        assert_eq!(config.ticks_to_counts(12), 33);
+        assert!(!config.matches(19, 8));
+        assert!(config.matches(20, 8));
+        assert!(config.matches(24, 8));
+        assert!(!config.matches(25, 8));
+        assert_eq!(config.half_bits(11), Some(1));
+        assert!(config.is_idle_down(53));
+    }
+
+    #[test]
+    fn zero_divisor() {
+        let config = DecoderConfig {
+            tick_len_divisor: 0,
+            ..DecoderConfig::new(22)
+        };
+        let config_1 = DecoderConfig::new(22);
+        for interval in [0, 100, 176, 264, 1894, u16::MAX] {
+            assert_eq!(config.matches(interval, 8), config_1.matches(interval, 8));
+            assert_eq!(
+                config.is_idle_down(interval),
+                config_1.is_idle_down(interval)
+            );
+        }
+    }
+
+    #[test]
+    fn no_overflow() {
+        let config = DecoderConfig::new(u16::MAX);
+        assert!(!config.is_idle_down(u16::MAX));
+        assert!(config.matches(u16::MAX, 1));
+    }
+}
@@ -0,0 +1,578 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::apdu::{Apdu, CommandApdu, ResponseApdu};
+use crate::frame::FrameAttributed;
+use crate::iso14443_4::InfAssembler;
+use crate::tlv::{find, parse_dol, split_dol_data, Tag, Tlv};
+
+/// Name of EMV data element, as listed in EMV Book 3 Annex A and
+/// contactless kernel specifications.
+pub fn tag_name(tag: Tag) -> Option<&'static str> {
+    let name = match tag.0 {
+        0x4F => "Application Identifier (AID)",
+        0x50 => "Application Label",
+        0x57 => "Track 2 Equivalent Data",
+        0x5A => "Application Primary Account Number (PAN)",
+        0x5F20 => "Cardholder Name",
+        0x5F24 => "Application Expiration Date",
+        0x5F25 => "Application Effective Date",
+        0x5F28 => "Issuer Country Code",
+        0x5F2A => "Transaction Currency Code",
+        0x5F2D => "Language Preference",
+        0x5F30 => "Service Code",
+        0x5F34 => "Application PAN Sequence Number",
+        0x61 => "Application Template",
+        0x6F => "File Control Information (FCI) Template",
+        0x70 => "READ RECORD Response Message Template",
+        0x77 => "Response Message Template Format 2",
+        0x80 => "Response Message Template Format 1",
+        0x82 => "Application Interchange Profile",
+        0x83 => "Command Template",
+        0x84 => "Dedicated File (DF) Name",
+        0x87 => "Application Priority Indicator",
+        0x88 => "Short File Identifier (SFI)",
+        0x8A => "Authorisation Response Code",
+        0x8C => "Card Risk Management Data Object List 1 (CDOL1)",
+        0x8D => "Card Risk Management Data Object List 2 (CDOL2)",
+        0x8E => "Cardholder Verification Method (CVM) List",
+        0x8F => "Certification Authority Public Key Index",
+        0x90 => "Issuer Public Key Certificate",
+        0x92 => "Issuer Public Key Remainder",
+        0x94 => "Application File Locator (AFL)",
+        0x95 => "Terminal Verification Results",
+        0x9A => "Transaction Date",
+        0x9C => "Transaction Type",
+        0x9D => "Directory Definition File (DDF) Name",
+        0x9F01 => "Acquirer Identifier",
+        0x9F02 => "Amount, Authorised (Numeric)",
+        0x9F03 => "Amount, Other (Numeric)",
+        0x9F06 => "Application Identifier (AID) - terminal",
+        0x9F07 => "Application Usage Control",
+        0x9F08 => "Application Version Number",
+        0x9F0D => "Issuer Action Code - Default",
+        0x9F0E => "Issuer Action Code - Denial",
+        0x9F0F => "Issuer Action Code - Online",
+        0x9F10 => "Issuer Application Data",
+        0x9F11 => "Issuer Code Table Index",
+        0x9F12 => "Application Preferred Name",
+        0x9F1A => "Terminal Country Code",
+        0x9F1F => "Track 1 Discretionary Data",
+        0x9F21 => "Transaction Time",
+        0x9F26 => "Application Cryptogram",
+        0x9F27 => "Cryptogram Information Data",
+        0x9F32 => "Issuer Public Key Exponent",
+        0x9F33 => "Terminal Capabilities",
+        0x9F34 => "Cardholder Verification Method (CVM) Results",
+        0x9F35 => "Terminal Type",
+        0x9F36 => "Application Transaction Counter (ATC)",
+        0x9F37 => "Unpredictable Number",
+        0x9F38 => "Processing Options Data Object List (PDOL)",
+        0x9F42 => "Application Currency Code",
+        0x9F44 => "Application Currency Exponent",
+        0x9F45 => "Data Authentication Code",
+        0x9F46 => "ICC Public Key Certificate",
+        0x9F47 => "ICC Public Key Exponent",
+        0x9F48 => "ICC Public Key Remainder",
+        0x9F49 => "Dynamic Data Authentication Data Object List (DDOL)",
+        0x9F4A => "Static Data Authentication Tag List",
+        0x9F4B => "Signed Dynamic Application Data",
+        0x9F4C => "ICC Dynamic Number",
+        0x9F4D => "Log Entry",
+        0x9F5D => "Available Offline Spending Amount",
+        0x9F66 => "Terminal Transaction Qualifiers (TTQ)",
+        0x9F6C => "Card Transaction Qualifiers (CTQ)",
+        0x9F6E => "Form Factor Indicator",
+        0x9F7C => "Customer Exclusive Data",
+        0xA5 => "File Control Information (FCI) Proprietary Template",
+        0xBF0C => "File Control Information (FCI) Issuer Discretionary Data",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Contactless kernel, as selected by the terminal from the registered
+/// application provider identifier (first 5 bytes of AID).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kernel {
+    /// Kernel 2, Mastercard.
+    Mastercard,
+    /// Kernel 3, Visa.
+    Visa,
+    /// Kernel 4, American Express.
+    Amex,
+    /// Kernel 5, JCB.
+    Jcb,
+    /// Kernel 6, Discover.
+    Discover,
+    /// Kernel 7, UnionPay.
+    UnionPay,
+    Other,
+}
+
+impl Kernel {
+    pub fn from_aid(aid: &[u8]) -> Self {
+        match aid.get(..5) {
+            Some([0xA0, 0x00, 0x00, 0x00, 0x04]) => Kernel::Mastercard,
+            Some([0xA0, 0x00, 0x00, 0x00, 0x03]) => Kernel::Visa,
+            Some([0xA0, 0x00, 0x00, 0x00, 0x25]) => Kernel::Amex,
+            Some([0xA0, 0x00, 0x00, 0x00, 0x65]) => Kernel::Jcb,
+            Some([0xA0, 0x00, 0x00, 0x01, 0x52]) => Kernel::Discover,
+            Some([0xA0, 0x00, 0x00, 0x03, 0x33]) => Kernel::UnionPay,
+            _ => Kernel::Other,
+        }
+    }
+
+    /// Kernel ID, as in EMV Contactless Book B.
+    pub fn id(&self) -> Option<u8> {
+        match self {
+            Kernel::Mastercard => Some(2),
+            Kernel::Visa => Some(3),
+            Kernel::Amex => Some(4),
+            Kernel::Jcb => Some(5),
+            Kernel::Discover => Some(6),
+            Kernel::UnionPay => Some(7),
+            Kernel::Other => None,
+        }
+    }
+}
+
+/// Application cryptogram type, from bits 8-7 of Cryptogram Information
+/// Data (9F27).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Cryptogram {
+    /// Application Authentication Cryptogram, transaction declined.
+    Aac,
+    /// Transaction Certificate, transaction approved offline.
+    Tc,
+    /// Authorisation Request Cryptogram, online authorisation requested.
+    Arqc,
+    Rfu,
+}
+
+impl Cryptogram {
+    pub fn from_cid(cid: u8) -> Self {
+        match cid >> 6 {
+            0b00 => Cryptogram::Aac,
+            0b01 => Cryptogram::Tc,
+            0b10 => Cryptogram::Arqc,
+            _ => Cryptogram::Rfu,
+        }
+    }
+}
+
+/// Application File Locator entry, range of records in one short file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AflEntry {
+    pub sfi: u8,
+    pub first_record: u8,
+    pub last_record: u8,
+    /// Number of records, starting from the first, used in offline data
+    /// authentication.
+    pub oda_records: u8,
+}
+
+impl AflEntry {
+    /// Entries of AFL (94) value; incomplete trailing entry is dropped.
+    pub fn parse_afl(afl: &[u8]) -> Vec<Self> {
+        afl.chunks_exact(4)
+            .map(|entry| AflEntry {
+                sfi: entry[0] >> 3,
+                first_record: entry[1],
+                last_record: entry[2],
+                oda_records: entry[3],
+            })
+            .collect()
+    }
+}
+
+/// Record received in answer to READ RECORD.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    pub sfi: u8,
+    pub number: u8,
+    pub tlvs: Vec<Tlv>,
+}
+
+/// Summary of one contactless payment, as seen by the sniffer.
+///
+/// Fields are filled in as the matching command and response pass; anything
+/// the card or the terminal did not send stays empty.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Transaction {
+    /// DF name of the application selected with success, other than PPSE.
+    pub aid: Option<Vec<u8>>,
+    pub kernel: Option<Kernel>,
+    pub pdol: Vec<(Tag, usize)>,
+    /// Values sent by the terminal in GET PROCESSING OPTIONS, split by PDOL.
+    pub pdol_data: Vec<(Tag, Vec<u8>)>,
+    pub aip: Option<[u8; 2]>,
+    pub afl: Vec<AflEntry>,
+    pub records: Vec<Record>,
+    pub cdol1: Vec<(Tag, usize)>,
+    /// Values sent by the terminal in GENERATE AC, split by CDOL1.
+    pub cdol1_data: Vec<(Tag, Vec<u8>)>,
+    pub cryptogram: Option<Cryptogram>,
+    /// Amount, Authorised (9F02), in minor units of the currency.
+    pub amount: Option<u64>,
+    /// Numeric ISO 4217 code of Transaction Currency Code (5F2A).
+    pub currency: Option<u16>,
+}
+
+impl Transaction {
+    pub fn from_frames(frames: &[FrameAttributed]) -> Self {
+        let mut tracker = TransactionTracker::new();
+        for frame in frames.iter() {
+            tracker.add_frame(frame);
+        }
+        tracker.transaction
+    }
+
+    fn take_terminal_data(&mut self, dol: &[(Tag, usize)], data: &[u8]) -> Vec<(Tag, Vec<u8>)> {
+        let Some(values) = split_dol_data(dol, data) else {
+            return Vec::new();
+        };
+        let mut out = Vec::with_capacity(values.len());
+        for (tag, value) in values.into_iter() {
+            match tag {
+                TAG_AMOUNT_AUTHORISED => self.amount = bcd_to_u64(value),
+                TAG_TRANSACTION_CURRENCY_CODE => {
+                    self.currency = bcd_to_u64(value).and_then(|code| code.try_into().ok())
+                }
+                _ => {}
+            }
+            out.push((tag, value.to_vec()));
+        }
+        out
+    }
+
+    fn take_cryptogram(&mut self, tlvs: &[Tlv]) {
+        if let Some([cid]) = find(tlvs, TAG_CRYPTOGRAM_INFORMATION_DATA).and_then(Tlv::bytes) {
+            self.cryptogram = Some(Cryptogram::from_cid(*cid));
+        }
+    }
+}
+
+const PPSE: &[u8] = b"2PAY.SYS.DDF01";
+
+const INS_SELECT: u8 = 0xA4;
+const INS_GET_PROCESSING_OPTIONS: u8 = 0xA8;
+const INS_READ_RECORD: u8 = 0xB2;
+const INS_GENERATE_AC: u8 = 0xAE;
+
+const TAG_AMOUNT_AUTHORISED: Tag = Tag(0x9F02);
+const TAG_AFL: Tag = Tag(0x94);
+const TAG_AIP: Tag = Tag(0x82);
+const TAG_CDOL1: Tag = Tag(0x8C);
+const TAG_COMMAND_TEMPLATE: Tag = Tag(0x83);
+const TAG_CRYPTOGRAM_INFORMATION_DATA: Tag = Tag(0x9F27);
+const TAG_DF_NAME: Tag = Tag(0x84);
+const TAG_PDOL: Tag = Tag(0x9F38);
+const TAG_RESPONSE_FORMAT_1: Tag = Tag(0x80);
+const TAG_TRANSACTION_CURRENCY_CODE: Tag = Tag(0x5F2A);
+
+/// Value of packed BCD digits, `None` if any nibble is not a digit or the
+/// value does not fit in `u64`.
+fn bcd_to_u64(data: &[u8]) -> Option<u64> {
+    data.iter().try_fold(0u64, |value, byte| {
+        let (high, low) = (byte >> 4, byte & 0x0F);
+        if (high > 9) | (low > 9) {
+            None
+        } else {
+            value
+                .checked_mul(100)?
+                .checked_add((high * 10 + low) as u64)
+        }
+    })
+}
+
+/// Follows APDUs of a contactless payment and collects [`Transaction`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TransactionTracker {
+    transaction: Transaction,
+    inf_assembler: InfAssembler,
+    /// Last command sent by PCD, waiting for response.
+    last_command: Option<CommandApdu>,
+}
+
+impl TransactionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    pub fn add_command(&mut self, command: &CommandApdu) {
+        self.last_command = Some(command.clone());
+    }
+
+    /// Responses with error status, or with no command to answer, are
+    /// skipped.
+    pub fn add_response(&mut self, response: &ResponseApdu) {
+        let Some(command) = self.last_command.take() else {
+            return;
+        };
+        if !response.is_success() {
+            return;
+        }
+        let tlvs = response.tlv().unwrap_or_default();
+        let transaction = &mut self.transaction;
+        match command.ins {
+            INS_SELECT if command.p1 == 0x04 => {
+                let df_name = find(&tlvs, TAG_DF_NAME)
+                    .and_then(Tlv::bytes)
+                    .unwrap_or(&command.data);
+                if df_name == PPSE {
+                    return;
+                }
+                transaction.aid = Some(df_name.to_vec());
+                transaction.kernel = Some(Kernel::from_aid(df_name));
+                transaction.pdol = find(&tlvs, TAG_PDOL)
+                    .and_then(Tlv::bytes)
+                    .and_then(|pdol| parse_dol(pdol).ok())
+                    .unwrap_or_default();
+            }
+            INS_GET_PROCESSING_OPTIONS => {
+                if let [tag, _, pdol_data @ ..] = command.data.as_slice() {
+                    if *tag as u32 == TAG_COMMAND_TEMPLATE.0 {
+                        let pdol = core::mem::take(&mut transaction.pdol);
+                        transaction.pdol_data = transaction.take_terminal_data(&pdol, pdol_data);
+                        transaction.pdol = pdol;
+                    }
+                }
+                let (aip, afl) = match find(&tlvs, TAG_RESPONSE_FORMAT_1).and_then(Tlv::bytes) {
+                    Some([aip_1, aip_2, afl @ ..]) => (Some([*aip_1, *aip_2]), Some(afl)),
+                    _ => (
+                        find(&tlvs, TAG_AIP)
+                            .and_then(Tlv::bytes)
+                            .and_then(|aip| aip.try_into().ok()),
+                        find(&tlvs, TAG_AFL).and_then(Tlv::bytes),
+                    ),
+                };
+                transaction.aip = aip;
+                transaction.afl = afl.map(AflEntry::parse_afl).unwrap_or_default();
+                transaction.take_cryptogram(&tlvs);
+            }
+            INS_READ_RECORD => {
+                if let Some(cdol1) = find(&tlvs, TAG_CDOL1).and_then(Tlv::bytes) {
+                    transaction.cdol1 = parse_dol(cdol1).unwrap_or_default();
+                }
+                transaction.records.push(Record {
+                    sfi: command.p2 >> 3,
+                    number: command.p1,
+                    tlvs,
+                });
+            }
+            INS_GENERATE_AC => {
+                let cdol1 = core::mem::take(&mut transaction.cdol1);
+                transaction.cdol1_data = transaction.take_terminal_data(&cdol1, &command.data);
+                transaction.cdol1 = cdol1;
+                match find(&tlvs, TAG_RESPONSE_FORMAT_1).and_then(Tlv::bytes) {
+                    Some([cid, ..]) => transaction.cryptogram = Some(Cryptogram::from_cid(*cid)),
+                    _ => transaction.take_cryptogram(&tlvs),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Frames are put together into APDUs with [`InfAssembler`]; frames
+    /// that are not ISO-DEP blocks and payloads that are not APDUs are
+    /// skipped.
+    pub fn add_frame(&mut self, frame: &FrameAttributed) {
+        let Some(payload) = self.inf_assembler.add_frame(frame) else {
+            return;
+        };
+        match Apdu::from_payload(&payload) {
+            Ok(Apdu::Command(command)) => self.add_command(&command),
+            Ok(Apdu::Response(response)) => self.add_response(&response),
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::Frame;
+
+    /// Wraps APDUs into I-blocks, alternating PCD and PICC.
+    fn session(apdus: &[&[u8]]) -> Vec<FrameAttributed> {
+        apdus
+            .iter()
+            .enumerate()
+            .map(|(i, apdu)| {
+                let mut block = vec![0x02 | ((i / 2) % 2) as u8];
+                block.extend_from_slice(apdu);
+                if i % 2 == 0 {
+                    FrameAttributed::Miller(Frame::Standard(block))
+                } else {
+                    FrameAttributed::Manchester(Frame::Standard(block))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn helpers() {
+        assert_eq!(
+            bcd_to_u64(&[0x00, 0x00, 0x00, 0x00, 0x12, 0x34]),
+            Some(1234)
+        );
+        assert_eq!(bcd_to_u64(&[0x1A]), None);
+        assert_eq!(bcd_to_u64(&[0x99; 10]), None);
+        assert_eq!(
+            bcd_to_u64(&[0x18, 0x44, 0x67, 0x44, 0x07, 0x37, 0x09, 0x55, 0x16, 0x15]),
+            Some(u64::MAX)
+        );
+        assert_eq!(Cryptogram::from_cid(0x80), Cryptogram::Arqc);
+        assert_eq!(Cryptogram::from_cid(0x40), Cryptogram::Tc);
+        assert_eq!(Cryptogram::from_cid(0x00), Cryptogram::Aac);
+        assert_eq!(
+            Kernel::from_aid(&[0xA0, 0x00, 0x00, 0x00, 0x03, 0x10, 0x10]),
+            Kernel::Visa
+        );
+        assert_eq!(Kernel::Visa.id(), Some(3));
+        assert_eq!(Kernel::from_aid(&[0xA0, 0x00]), Kernel::Other);
+    }
+
+    fn mastercard_frames() -> Vec<FrameAttributed> {
+        let mut select_ppse = vec![0x00, 0xA4, 0x04, 0x00, 0x0E];
+        select_ppse.extend_from_slice(PPSE);
+        select_ppse.push(0x00);
+        session(&[
+            &select_ppse,
+            &[0x6A, 0x82],
+            &[
+                0x00, 0xA4, 0x04, 0x00, 0x07, 0xA0, 0x00, 0x00, 0x00, 0x04, 0x10, 0x10, 0x00,
+            ],
+            &[
+                0x6F, 0x14, 0x84, 0x07, 0xA0, 0x00, 0x00, 0x00, 0x04, 0x10, 0x10, 0xA5, 0x09, 0x9F,
+                0x38, 0x06, 0x9F, 0x02, 0x06, 0x5F, 0x2A, 0x02, 0x90, 0x00,
+            ],
+            &[
+                0x80, 0xA8, 0x00, 0x00, 0x0A, 0x83, 0x08, 0x00, 0x00, 0x00, 0x00, 0x12, 0x34, 0x09,
+                0x78, 0x00,
+            ],
+            &[
+                0x77, 0x0A, 0x82, 0x02, 0x19, 0x80, 0x94, 0x04, 0x08, 0x01, 0x01, 0x00, 0x90, 0x00,
+            ],
+            &[0x00, 0xB2, 0x01, 0x0C, 0x00],
+            &[
+                0x70, 0x0A, 0x8C, 0x08, 0x9F, 0x02, 0x06, 0x5F, 0x2A, 0x02, 0x9A, 0x03, 0x90, 0x00,
+            ],
+            &[
+                0x80, 0xAE, 0x80, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x00, 0x12, 0x34, 0x09, 0x78, 0x26,
+                0x10, 0x17, 0x00,
+            ],
+            &[0x77, 0x04, 0x9F, 0x27, 0x01, 0x80, 0x90, 0x00],
+        ])
+    }
+
+    #[test]
+    fn mastercard_tap() {
+        let transaction = Transaction::from_frames(&mastercard_frames());
+        assert_eq!(
+            transaction.aid,
+            Some(vec![0xA0, 0x00, 0x00, 0x00, 0x04, 0x10, 0x10])
+        );
+        assert_eq!(transaction.kernel, Some(Kernel::Mastercard));
+        assert_eq!(
+            transaction.pdol_data,
+            vec![
+                (Tag(0x9F02), vec![0x00, 0x00, 0x00, 0x00, 0x12, 0x34]),
+                (Tag(0x5F2A), vec![0x09, 0x78]),
+            ]
+        );
+        assert_eq!(transaction.aip, Some([0x19, 0x80]));
+        assert_eq!(
+            transaction.afl,
+            vec![AflEntry {
+                sfi: 1,
+                first_record: 1,
+                last_record: 1,
+                oda_records: 0,
+            }]
+        );
+        assert_eq!(transaction.records.len(), 1);
+        assert_eq!(
+            (transaction.records[0].sfi, transaction.records[0].number),
+            (1, 1)
+        );
+        assert_eq!(
+            transaction.cdol1,
+            vec![(Tag(0x9F02), 6), (Tag(0x5F2A), 2), (Tag(0x9A), 3)]
+        );
+        assert_eq!(
+            transaction.cdol1_data[2],
+            (Tag(0x9A), vec![0x26, 0x10, 0x17])
+        );
+        assert_eq!(transaction.cryptogram, Some(Cryptogram::Arqc));
+        assert_eq!(transaction.amount, Some(1234));
+        assert_eq!(transaction.currency, Some(978));
+    }
+
+    #[test]
+    fn mastercard_tap_after_rats() {
+        // ATS with TL 0x0A looks like an I-block
+        let mut frames = vec![
+            FrameAttributed::Miller(Frame::Standard(vec![0xE0, 0x80])),
+            FrameAttributed::Manchester(Frame::Standard(vec![
+                0x0A, 0x78, 0x77, 0x91, 0x02, 0x80, 0x31, 0x80, 0x66, 0xB0,
+            ])),
+        ];
+        frames.extend(mastercard_frames());
+        let transaction = Transaction::from_frames(&frames);
+        assert_eq!(
+            transaction.aid,
+            Some(vec![0xA0, 0x00, 0x00, 0x00, 0x04, 0x10, 0x10])
+        );
+        assert_eq!(transaction, Transaction::from_frames(&mastercard_frames()));
+    }
+
+    #[test]
+    fn oversized_amount() {
+        let frames = session(&[
+            &[
+                0x00, 0xA4, 0x04, 0x00, 0x07, 0xA0, 0x00, 0x00, 0x00, 0x04, 0x10, 0x10, 0x00,
+            ],
+            &[
+                0x6F, 0x11, 0x84, 0x07, 0xA0, 0x00, 0x00, 0x00, 0x04, 0x10, 0x10, 0xA5, 0x06, 0x9F,
+                0x38, 0x03, 0x9F, 0x02, 0x0C, 0x90, 0x00,
+            ],
+            &[
+                0x80, 0xA8, 0x00, 0x00, 0x0E, 0x83, 0x0C, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99,
+                0x99, 0x99, 0x99, 0x99, 0x99, 0x00,
+            ],
+            &[
+                0x77, 0x0A, 0x82, 0x02, 0x19, 0x80, 0x94, 0x04, 0x08, 0x01, 0x01, 0x00, 0x90, 0x00,
+            ],
+        ]);
+        let transaction = Transaction::from_frames(&frames);
+        assert_eq!(transaction.pdol_data, vec![(Tag(0x9F02), vec![0x99; 12])]);
+        assert_eq!(transaction.aip, Some([0x19, 0x80]));
+        assert_eq!(transaction.amount, None);
+    }
+
+    #[test]
+    fn visa_format_1_and_failed_select() {
+        let frames = session(&[
+            &[
+                0x00, 0xA4, 0x04, 0x00, 0x07, 0xA0, 0x00, 0x00, 0x00, 0x03, 0x10, 0x10, 0x00,
+            ],
+            &[0x6A, 0x82],
+            &[0x80, 0xAE, 0x40, 0x00, 0x00],
+            &[0x80, 0x03, 0x40, 0x00, 0x01, 0x90, 0x00],
+        ]);
+        let transaction = Transaction::from_frames(&frames);
+        assert_eq!(transaction.aid, None);
+        assert_eq!(transaction.kernel, None);
+        assert_eq!(transaction.cryptogram, Some(Cryptogram::Tc));
+        assert_eq!(transaction.amount, None);
+    }
+}
@@ -1,3 +1,20 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::manchester::ManchesterElement;
+use crate::miller_reworked::MillerElement;
+use crate::tlv::MAX_DEPTH;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ApduError {
+    TooShort,
+    WrongLength,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum FrameError {
     CrcMismatch,
@@ -5,11 +22,23 @@ pub enum FrameError {
     ParityBit,
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub enum IsoDepError {
+    AtsLength,
+    EmptyBlock,
+    NotStandardFrame,
+    Truncated,
+    UnexpectedInf,
+    UnknownPcb(u8),
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum ManchesterError {
+    BitCollision,
     FirstNotD,
     Frame(FrameError),
     IncompleteFrame,
+    IntervalOverflow,
     NoAddingToComplete,
     UnexpectedOddInterval(u16),
     UnexpectedEvenInterval(u16),
@@ -19,7 +48,305 @@ pub enum ManchesterError {
 pub enum MillerError {
     Frame(FrameError),
     IncompleteFrame,
+    IntervalOverflow,
     UnexpectedInterval(u16),
     UnexpectedMillerOffInterval(u16),
     WrongMillerSequence,
 }
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum TlvError {
+    IndefiniteLength,
+    LengthTooLong,
+    TagTooLong,
+    TooDeep,
+    TruncatedLength,
+    TruncatedTag,
+    TruncatedValue,
+}
+
+/// Number of last elements kept in [`ErrorPosition`].
+pub const LAST_ELEMENTS: usize = 4;
+
+/// Where in the input the decoding failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorPosition<T> {
+    /// Index of the failed interval in the buffer.
+    pub offset: usize,
+    /// Index of the chunk (intervals between idle gaps) in the buffer.
+    pub chunk: usize,
+    /// Bit periods decoded in the frame after the start of frame.
+    pub bits: usize,
+    /// Up to [`LAST_ELEMENTS`] elements seen before the failure, oldest first.
+    pub last_elements: Vec<T>,
+}
+
+impl<T: Copy> ErrorPosition<T> {
+    pub(crate) fn new(offset: usize, chunk: usize, element_set: &[T]) -> Self {
+        Self {
+            offset,
+            chunk,
+            bits: element_set.len().saturating_sub(1),
+            last_elements: element_set[element_set.len().saturating_sub(LAST_ELEMENTS)..].to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ManchesterDecodeError {
+    pub error: ManchesterError,
+    pub position: ErrorPosition<ManchesterElement>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct MillerDecodeError {
+    pub error: MillerError,
+    pub position: ErrorPosition<MillerElement>,
+}
+
+/// Any error produced in this crate.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    Apdu(ApduError),
+    Frame(FrameError),
+    IsoDep(IsoDepError),
+    Manchester(ManchesterError),
+    ManchesterDecode(ManchesterDecodeError),
+    Miller(MillerError),
+    MillerDecode(MillerDecodeError),
+    Tlv(TlvError),
+}
+
+impl From<ApduError> for Error {
+    fn from(error: ApduError) -> Self {
+        Error::Apdu(error)
+    }
+}
+
+impl From<FrameError> for Error {
+    fn from(error: FrameError) -> Self {
+        Error::Frame(error)
+    }
+}
+
+impl From<IsoDepError> for Error {
+    fn from(error: IsoDepError) -> Self {
+        Error::IsoDep(error)
+    }
+}
+
+impl From<ManchesterError> for Error {
+    fn from(error: ManchesterError) -> Self {
+        Error::Manchester(error)
+    }
+}
+
+impl From<ManchesterDecodeError> for Error {
+    fn from(error: ManchesterDecodeError) -> Self {
+        Error::ManchesterDecode(error)
+    }
+}
+
+impl From<MillerError> for Error {
+    fn from(error: MillerError) -> Self {
+        Error::Miller(error)
+    }
+}
+
+impl From<MillerDecodeError> for Error {
+    fn from(error: MillerDecodeError) -> Self {
+        Error::MillerDecode(error)
+    }
+}
+
+impl From<TlvError> for Error {
+    fn from(error: TlvError) -> Self {
+        Error::Tlv(error)
+    }
+}
+
+impl fmt::Display for ApduError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ApduError::TooShort => write!(f, "APDU is too short"),
+            ApduError::WrongLength => write!(f, "APDU length does not match Lc and Le fields"),
+        }
+    }
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrameError::CrcMismatch => write!(f, "CRC_A mismatch"),
+            FrameError::EmptyFrame => write!(f, "frame contains no bits"),
+            FrameError::ParityBit => write!(f, "wrong parity bit"),
+        }
+    }
+}
+
+impl fmt::Display for IsoDepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IsoDepError::AtsLength => write!(f, "ATS length byte does not match ATS length"),
+            IsoDepError::EmptyBlock => write!(f, "ISO-DEP block contains no bytes"),
+            IsoDepError::NotStandardFrame => write!(f, "ISO-DEP block is not a frame with CRC_A"),
+            IsoDepError::Truncated => write!(f, "ISO-DEP block ends in prologue"),
+            IsoDepError::UnexpectedInf => write!(f, "unexpected INF field in ISO-DEP block"),
+            IsoDepError::UnknownPcb(pcb) => write!(f, "unknown ISO-DEP PCB {pcb:#04x}"),
+        }
+    }
+}
+
+impl fmt::Display for ManchesterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ManchesterError::BitCollision => write!(f, "bit collision in Manchester frame"),
+            ManchesterError::FirstNotD => write!(f, "Manchester sequence does not start with D"),
+            ManchesterError::Frame(error) => write!(f, "Manchester frame error: {error}"),
+            ManchesterError::IncompleteFrame => write!(f, "Manchester frame has no end"),
+            ManchesterError::IntervalOverflow => {
+                write!(f, "Manchester interval does not fit in timer counts")
+            }
+            ManchesterError::NoAddingToComplete => {
+                write!(f, "Manchester element added to complete frame")
+            }
+            ManchesterError::UnexpectedOddInterval(interval) => {
+                write!(f, "unexpected odd Manchester interval {interval}")
+            }
+            ManchesterError::UnexpectedEvenInterval(interval) => {
+                write!(f, "unexpected even Manchester interval {interval}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for MillerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MillerError::Frame(error) => write!(f, "Miller frame error: {error}"),
+            MillerError::IncompleteFrame => write!(f, "Miller frame has no end"),
+            MillerError::IntervalOverflow => {
+                write!(f, "Miller interval does not fit in timer counts")
+            }
+            MillerError::UnexpectedInterval(interval) => {
+                write!(f, "unexpected Miller interval {interval}")
+            }
+            MillerError::UnexpectedMillerOffInterval(interval) => {
+                write!(f, "unexpected Miller pause length {interval}")
+            }
+            MillerError::WrongMillerSequence => write!(f, "invalid Miller element sequence"),
+        }
+    }
+}
+
+impl fmt::Display for TlvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TlvError::IndefiniteLength => write!(f, "indefinite TLV length is not supported"),
+            TlvError::LengthTooLong => write!(f, "TLV length field is longer than 4 bytes"),
+            TlvError::TagTooLong => write!(f, "TLV tag is longer than 4 bytes"),
+            TlvError::TooDeep => write!(f, "TLV nesting is deeper than {MAX_DEPTH} levels"),
+            TlvError::TruncatedLength => write!(f, "TLV ends in length field"),
+            TlvError::TruncatedTag => write!(f, "TLV ends in tag field"),
+            TlvError::TruncatedValue => write!(f, "TLV value is shorter than its length"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for ErrorPosition<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "interval {} in chunk {}, after {} bits, last elements {:?}",
+            self.offset, self.chunk, self.bits, self.last_elements
+        )
+    }
+}
+
+impl fmt::Display for ManchesterDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.error, self.position)
+    }
+}
+
+impl fmt::Display for MillerDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.error, self.position)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Apdu(error) => write!(f, "{error}"),
+            Error::Frame(error) => write!(f, "{error}"),
+            Error::IsoDep(error) => write!(f, "{error}"),
+            Error::Manchester(error) => write!(f, "{error}"),
+            Error::ManchesterDecode(error) => write!(f, "{error}"),
+            Error::Miller(error) => write!(f, "{error}"),
+            Error::MillerDecode(error) => write!(f, "{error}"),
+            Error::Tlv(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl core::error::Error for ApduError {}
+
+impl core::error::Error for FrameError {}
+
+impl core::error::Error for IsoDepError {}
+
+// Display of the nested errors includes the inner error, so `source` is left
+// at default `None` to avoid printing it twice in error chains.
+
+impl core::error::Error for ManchesterError {}
+
+impl core::error::Error for MillerError {}
+
+impl core::error::Error for ManchesterDecodeError {}
+
+impl core::error::Error for MillerDecodeError {}
+
+impl core::error::Error for TlvError {}
+
+impl core::error::Error for Error {}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn display_nested() {
+        let error = Error::from(MillerError::Frame(FrameError::ParityBit));
+        assert_eq!(error.to_string(), "Miller frame error: wrong parity bit");
+        assert!(core::error::Error::source(&error).is_none());
+    }
+
+    #[test]
+    fn display_position() {
+        let error = MillerDecodeError {
+            error: MillerError::UnexpectedInterval(60),
+            position: ErrorPosition {
+                offset: 3,
+                chunk: 1,
+                bits: 1,
+                last_elements: vec![MillerElement::Z, MillerElement::Z],
+            },
+        };
+        assert_eq!(
+            error.to_string(),
+            "unexpected Miller interval 60 at interval 3 in chunk 1, after 1 bits, last elements [Z, Z]"
+        );
+        assert!(core::error::Error::source(&error).is_none());
+    }
+
+    #[test]
+    fn question_mark() {
+        fn decode() -> Result<(), Error> {
+            Err(FrameError::EmptyFrame)?
+        }
+        assert_eq!(decode(), Err(Error::Frame(FrameError::EmptyFrame)));
+    }
+}
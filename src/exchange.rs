@@ -0,0 +1,145 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::fdt::{Fdt, FdtConfig};
+use crate::frame::{TimedFrame, TimedFrameAttributed};
+
+/// PCD command and PICC answer to it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Exchange {
+    Answered {
+        command: TimedFrame,
+        response: TimedFrame,
+    },
+    /// PCD frame with no PICC frame within timeout.
+    Unanswered(TimedFrame),
+    /// PICC frame with no PCD frame to answer to.
+    Orphan(TimedFrame),
+}
+
+impl Exchange {
+    /// Pair each PCD frame with PICC frame starting within `timeout` after
+    /// the PCD frame end; `timeout` is in timer counts.
+    ///
+    /// Frames are expected in time order. Only the first PICC frame is taken
+    /// as an answer, later ones are orphans.
+    pub fn pair(frames: &[TimedFrameAttributed], timeout: u64) -> Vec<Self> {
+        let mut exchanges = Vec::new();
+        let mut pending: Option<&TimedFrame> = None;
+        for frame in frames.iter() {
+            match frame {
+                TimedFrameAttributed::Miller(command) => {
+                    if let Some(previous) = pending.replace(command) {
+                        exchanges.push(Exchange::Unanswered(previous.clone()))
+                    }
+                }
+                TimedFrameAttributed::Manchester(response) => match pending.take() {
+                    Some(command) if response.start.saturating_sub(command.end) <= timeout => {
+                        exchanges.push(Exchange::Answered {
+                            command: command.clone(),
+                            response: response.clone(),
+                        })
+                    }
+                    Some(command) => {
+                        exchanges.push(Exchange::Unanswered(command.clone()));
+                        exchanges.push(Exchange::Orphan(response.clone()))
+                    }
+                    None => exchanges.push(Exchange::Orphan(response.clone())),
+                },
+            }
+        }
+        if let Some(command) = pending {
+            exchanges.push(Exchange::Unanswered(command.clone()))
+        }
+        exchanges
+    }
+
+    pub fn command(&self) -> Option<&TimedFrame> {
+        match self {
+            Exchange::Answered { command, .. } => Some(command),
+            Exchange::Unanswered(command) => Some(command),
+            Exchange::Orphan(_) => None,
+        }
+    }
+
+    pub fn response(&self) -> Option<&TimedFrame> {
+        match self {
+            Exchange::Answered { response, .. } => Some(response),
+            Exchange::Unanswered(_) => None,
+            Exchange::Orphan(response) => Some(response),
+        }
+    }
+
+    /// Frame delay time of the answer.
+    pub fn fdt(&self, config: &FdtConfig) -> Option<Fdt> {
+        match self {
+            Exchange::Answered { command, response } => {
+                Some(Fdt::measure(command, response, config))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::Frame;
+
+    fn miller(frame: Frame, start: u64, end: u64) -> TimedFrameAttributed {
+        TimedFrameAttributed::Miller(TimedFrame { frame, start, end })
+    }
+
+    fn manchester(frame: Frame, start: u64, end: u64) -> TimedFrameAttributed {
+        TimedFrameAttributed::Manchester(TimedFrame { frame, start, end })
+    }
+
+    #[test]
+    fn pairing() {
+        let frames = [
+            manchester(Frame::Standard(vec![0x08]), 0, 500),
+            miller(Frame::Short(0x52), 10_000, 11_000),
+            manchester(Frame::SddCleanCut(vec![0x44, 0x00]), 12_700, 15_000),
+            manchester(Frame::SddCleanCut(vec![0x44, 0x00]), 16_000, 18_000),
+            miller(Frame::Short(0x52), 30_000, 31_000),
+            miller(Frame::Short(0x52), 40_000, 41_000),
+            manchester(Frame::SddCleanCut(vec![0x44, 0x00]), 49_000, 51_000),
+            miller(Frame::SddCleanCut(vec![0x93, 0x20]), 60_000, 61_000),
+        ];
+        let exchanges = Exchange::pair(&frames, 5000);
+        assert_eq!(
+            exchanges,
+            vec![
+                Exchange::Orphan(frames[0].timed_frame().clone()),
+                Exchange::Answered {
+                    command: frames[1].timed_frame().clone(),
+                    response: frames[2].timed_frame().clone(),
+                },
+                Exchange::Orphan(frames[3].timed_frame().clone()),
+                Exchange::Unanswered(frames[4].timed_frame().clone()),
+                Exchange::Unanswered(frames[5].timed_frame().clone()),
+                Exchange::Orphan(frames[6].timed_frame().clone()),
+                Exchange::Unanswered(frames[7].timed_frame().clone()),
+            ]
+        );
+        assert_eq!(exchanges[1].response(), Some(frames[2].timed_frame()));
+        assert_eq!(exchanges[3].response(), None);
+        assert_eq!(exchanges[5].command(), None);
+    }
+
+    #[test]
+    fn pairing_fdt() {
+        let frames = [
+            miller(Frame::Short(0x52), 0, 1000),
+            manchester(Frame::SddCleanCut(vec![0x44, 0x00]), 1000 + 1236 + 98, 4000),
+        ];
+        let config = FdtConfig::new(16);
+        let exchanges = Exchange::pair(&frames, 5000);
+        let fdt = exchanges[0].fdt(&config).unwrap();
+        assert_eq!(fdt.deviation(), 0);
+        assert_eq!(fdt.violation(&config), None);
+    }
+}
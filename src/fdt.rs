@@ -0,0 +1,305 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::config::DecoderConfig;
+use crate::frame::{TimedFrame, TimedFrameAttributed};
+use crate::iso14443_3::PcdCommand;
+
+/// Carrier cycles in a tick, 8 ticks are a bit period of 128/fc.
+const FC_PER_TICK: i64 = 16;
+
+/// Carrier cycles in a bit period at 106 kbit/s.
+pub const FC_PER_BIT: i64 = 128;
+
+/// FDT part after whole bit periods, if the last PCD bit is logic 1.
+pub const FDT_OFFSET_LAST_1: i64 = 84;
+
+/// FDT part after whole bit periods, if the last PCD bit is logic 0.
+pub const FDT_OFFSET_LAST_0: i64 = 20;
+
+/// Bit periods in FDT for answers to REQA, WUPA, ANTICOLLISION and SELECT,
+/// and the shortest FDT for all other commands.
+pub const FDT_N_MIN: i64 = 9;
+
+/// Expected frame delay time PCD to PICC, in carrier cycles.
+pub fn expected_fdt(n: i64, last_bit: bool) -> i64 {
+    n * FC_PER_BIT
+        + if last_bit {
+            FDT_OFFSET_LAST_1
+        } else {
+            FDT_OFFSET_LAST_0
+        }
+}
+
+/// Parameters for conversion of frame times into FDT.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FdtConfig {
+    /// Configuration the frames were decoded with; its `tick_len` and
+    /// `tick_len_divisor` set the timer clock.
+    pub decoder: DecoderConfig,
+
+    /// Acceptable deviation from the expected FDT, in carrier cycles.
+    pub tolerance: i64,
+
+    /// Length of PCD pause, in carrier cycles.
+    ///
+    /// FDT starts at the end of the last PCD pause, and the time of PCD frame
+    /// end is the start of the pause. ISO 14443-2 sets the pause length `t1`
+    /// between 28/fc and 40.5/fc.
+    pub pause_len: i64,
+
+    /// Time from the first modulation edge of PICC start bit to the start of
+    /// PICC frame, in carrier cycles.
+    ///
+    /// Both-edge record starts after the modulated first half of start of
+    /// frame, i.e. 64/fc after the first modulation edge.
+    pub picc_start_delay: i64,
+}
+
+impl FdtConfig {
+    pub const fn new(tick_len: u16) -> Self {
+        Self::from_decoder_config(DecoderConfig::new(tick_len))
+    }
+
+    /// Configuration for frames decoded with `decoder`.
+    pub const fn from_decoder_config(decoder: DecoderConfig) -> Self {
+        Self {
+            decoder,
+            tolerance: FC_PER_TICK,
+            pause_len: 34,
+            picc_start_delay: FC_PER_BIT / 2,
+        }
+    }
+
+    /// Timer counts into carrier cycles, rounded.
+    pub fn counts_to_fc(&self, counts: i64) -> i64 {
+        let tick_len = self.decoder.tick_len.max(1) as i64;
+        let divisor = self.decoder.tick_len_divisor.max(1) as i64;
+        (counts * FC_PER_TICK * divisor + tick_len / 2).div_euclid(tick_len)
+    }
+}
+
+/// Timing problem with PICC answer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FdtViolation {
+    /// Answer is earlier than the shortest FDT.
+    TooEarly,
+    /// Answer is not aligned to the bit grid of PCD frame, deviation from
+    /// the nearest valid FDT in carrier cycles.
+    OffGrid(i64),
+    /// Answer to REQA, WUPA, ANTICOLLISION or SELECT is not in the first
+    /// slot; could indicate relay.
+    LateSlot(i64),
+}
+
+/// Frame delay time between PCD frame and PICC answer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Fdt {
+    /// Measured FDT, in carrier cycles.
+    pub measured: i64,
+    /// Nearest valid FDT, in carrier cycles.
+    pub expected: i64,
+    /// Bit periods in `expected`.
+    pub n: i64,
+    /// Last data bit sent by PCD.
+    pub last_bit: bool,
+    /// PICC must answer with exactly [`FDT_N_MIN`] bit periods.
+    pub fixed: bool,
+}
+
+impl Fdt {
+    /// FDT between PCD frame and PICC frame following it.
+    pub fn measure(pcd: &TimedFrame, picc: &TimedFrame, config: &FdtConfig) -> Self {
+        let last_bit = pcd.frame.to_bits().last().is_some_and(|bit| *bit);
+        let fixed = matches!(
+            PcdCommand::from_frame(&pcd.frame),
+            PcdCommand::Reqa
+                | PcdCommand::Wupa
+                | PcdCommand::Anticollision { .. }
+                | PcdCommand::Select { .. }
+        );
+        let measured = config.counts_to_fc(picc.start as i64 - pcd.end as i64)
+            - config.pause_len
+            - config.picc_start_delay;
+        let offset = expected_fdt(0, last_bit);
+        let n = if fixed {
+            FDT_N_MIN
+        } else {
+            ((measured - offset + FC_PER_BIT / 2).div_euclid(FC_PER_BIT)).max(FDT_N_MIN)
+        };
+        Self {
+            measured,
+            expected: expected_fdt(n, last_bit),
+            n,
+            last_bit,
+            fixed,
+        }
+    }
+
+    /// Measured minus expected FDT, in carrier cycles.
+    pub fn deviation(&self) -> i64 {
+        self.measured - self.expected
+    }
+
+    pub fn violation(&self, config: &FdtConfig) -> Option<FdtViolation> {
+        let deviation = self.deviation();
+        if deviation < -config.tolerance {
+            if self.n == FDT_N_MIN {
+                Some(FdtViolation::TooEarly)
+            } else {
+                Some(FdtViolation::OffGrid(deviation))
+            }
+        } else if deviation <= config.tolerance {
+            None
+        } else if self.fixed {
+            let slot = (deviation + FC_PER_BIT / 2).div_euclid(FC_PER_BIT);
+            let grid_deviation = deviation - slot * FC_PER_BIT;
+            if (slot > 0) & (grid_deviation.abs() <= config.tolerance) {
+                Some(FdtViolation::LateSlot(slot))
+            } else {
+                Some(FdtViolation::OffGrid(deviation))
+            }
+        } else {
+            Some(FdtViolation::OffGrid(deviation))
+        }
+    }
+}
+
+/// FDT of PICC answer, with the frames it is measured between.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FdtReport {
+    pub pcd: TimedFrame,
+    pub picc: TimedFrame,
+    pub fdt: Fdt,
+    pub violation: Option<FdtViolation>,
+}
+
+/// FDT for each PCD frame immediately followed by PICC frame.
+pub fn check_session(frames: &[TimedFrameAttributed], config: &FdtConfig) -> Vec<FdtReport> {
+    frames
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [TimedFrameAttributed::Miller(pcd), TimedFrameAttributed::Manchester(picc)] => {
+                let fdt = Fdt::measure(pcd, picc, config);
+                Some(FdtReport {
+                    pcd: pcd.clone(),
+                    picc: picc.clone(),
+                    fdt,
+                    violation: fdt.violation(config),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::Frame;
+
+    fn timed(frame: Frame, start: u64, end: u64) -> TimedFrame {
+        TimedFrame { frame, start, end }
+    }
+
+    #[test]
+    fn counts_to_fc() {
+        let config = FdtConfig::new(22);
+        assert_eq!(config.counts_to_fc(22), 16);
+        assert_eq!(config.counts_to_fc(1746), 1270);
+        assert_eq!(config.counts_to_fc(-22), -16);
+
+        let config = FdtConfig::from_decoder_config(DecoderConfig {
+            tick_len_divisor: 2,
+            ..DecoderConfig::new(44)
+        });
+        assert_eq!(config.counts_to_fc(22), 16);
+        assert_eq!(config.counts_to_fc(1746), 1270);
+
+        let config = FdtConfig::new(0);
+        assert_eq!(config.counts_to_fc(1), 16);
+    }
+
+    #[test]
+    fn fdt_reqa() {
+        // timer at 13.56 MHz, single count is 1/fc
+        let config = FdtConfig::new(16);
+        let reqa = timed(Frame::Short(0x26), 0, 1000);
+        // last bit of REQA is 0, FDT is 1172/fc
+        let atqa = timed(
+            Frame::SddCleanCut(vec![0x04, 0x00]),
+            1000 + 1172 + 34 + 64,
+            4000,
+        );
+        let fdt = Fdt::measure(&reqa, &atqa, &config);
+        assert_eq!(
+            fdt,
+            Fdt {
+                measured: 1172,
+                expected: 1172,
+                n: 9,
+                last_bit: false,
+                fixed: true,
+            }
+        );
+        assert_eq!(fdt.violation(&config), None);
+
+        let relayed = timed(atqa.frame.clone(), atqa.start + 3 * 128 + 5, 4000);
+        let fdt = Fdt::measure(&reqa, &relayed, &config);
+        assert_eq!(fdt.deviation(), 389);
+        assert_eq!(fdt.violation(&config), Some(FdtViolation::LateSlot(3)));
+
+        let early = timed(atqa.frame.clone(), atqa.start - 40, 4000);
+        let fdt = Fdt::measure(&reqa, &early, &config);
+        assert_eq!(fdt.violation(&config), Some(FdtViolation::TooEarly));
+    }
+
+    #[test]
+    fn fdt_free_slot() {
+        let config = FdtConfig::new(16);
+        let rats = timed(Frame::Standard(vec![0xE0, 0x80]), 0, 1000);
+        let last_bit = *rats.frame.to_bits().last().unwrap();
+        let ats_start = 1000 + expected_fdt(40, last_bit) as u64 + 34 + 64;
+        let ats = timed(
+            Frame::Standard(vec![0x05, 0x78, 0x80, 0x70, 0x02]),
+            ats_start,
+            9000,
+        );
+        let fdt = Fdt::measure(&rats, &ats, &config);
+        assert!(!fdt.fixed);
+        assert_eq!(fdt.n, 40);
+        assert_eq!(fdt.violation(&config), None);
+
+        let ats_off_grid = timed(ats.frame.clone(), ats_start + 50, 9000);
+        let fdt = Fdt::measure(&rats, &ats_off_grid, &config);
+        assert_eq!(fdt.violation(&config), Some(FdtViolation::OffGrid(50)));
+    }
+
+    #[test]
+    fn session() {
+        let config = FdtConfig::new(16);
+        let frames = [
+            TimedFrameAttributed::Miller(timed(Frame::Short(0x52), 0, 1000)),
+            TimedFrameAttributed::Manchester(timed(
+                Frame::SddCleanCut(vec![0x44, 0x00]),
+                1000 + 1236 + 98,
+                4000,
+            )),
+            TimedFrameAttributed::Miller(timed(Frame::Short(0x52), 10000, 11000)),
+            TimedFrameAttributed::Miller(timed(Frame::SddCleanCut(vec![0x93, 0x20]), 20000, 21000)),
+            TimedFrameAttributed::Manchester(timed(
+                Frame::SddCleanCut(vec![0x88, 0x04, 0x21, 0x32, 0x9F]),
+                21000 + 1172 + 98 + 128,
+                26000,
+            )),
+        ];
+        let reports = check_session(&frames, &config);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].violation, None);
+        assert!(!reports[1].fdt.last_bit);
+        assert_eq!(reports[1].violation, Some(FdtViolation::LateSlot(1)));
+    }
+}
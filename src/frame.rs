@@ -11,7 +11,7 @@ use crate::error::FrameError;
 
 pub const EDC_CALC: Crc<u16> = Crc::<u16>::new(&CRC_16_ISO_IEC_14443_3_A);
 
-#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum Frame {
     Short(u8),
     SddBits(BitVec<u8, Lsb0>),
@@ -19,6 +19,66 @@ pub enum Frame {
     Standard(Vec<u8>),
 }
 
+impl Frame {
+    /// Bits of the frame as transmitted, without start and end of frame.
+    ///
+    /// Each byte is followed by odd parity bit, `Standard` frames get CRC_A
+    /// appended. `Short` frame is 7 bits long: only the low 7 bits of the
+    /// value are sent, the top bit is dropped.
+    ///
+    /// [`CompleteCollector::to_frame`] recovers the same frame from the bits,
+    /// except for:
+    /// - `Short` frames with value above 0x7F,
+    /// - `SddBits` frames of fewer than 8 bits, read back as `Short`,
+    /// - `SddBits` frames of 9·k bits, read back as bytes with parity,
+    /// - `SddCleanCut` frames ending with valid CRC_A,
+    /// - `Standard` frames with no data.
+    pub fn to_bits(&self) -> BitVec<u8, Lsb0> {
+        match self {
+            Frame::Short(byte) => {
+                let mut bits = BitVec::<u8, Lsb0>::from_element(*byte);
+                bits.truncate(7);
+                bits
+            }
+            Frame::SddBits(bits) => bits.to_owned(),
+            Frame::SddCleanCut(data) => bytes_with_parity(data),
+            Frame::Standard(data) => {
+                let crc = EDC_CALC.checksum(data).to_le_bytes();
+                bytes_with_parity(&[data.as_slice(), &crc].concat())
+            }
+        }
+    }
+}
+
+/// Decoded frame, with time of its first and last edge in the record.
+///
+/// Times are in timer counts, summed from the first edge in the buffer and
+/// shifted by capture start time given to the decoder. `end` is the start of
+/// the last pause for Miller frame and the end of the last modulated half-bit
+/// for Manchester frame, i.e. the edge frame delay time is counted from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimedFrame {
+    pub frame: Frame,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl TimedFrame {
+    /// Frame length, in timer counts.
+    pub fn duration(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
+fn bytes_with_parity(data: &[u8]) -> BitVec<u8, Lsb0> {
+    let mut bits = BitVec::<u8, Lsb0>::with_capacity(data.len() * 9);
+    for byte in data.iter() {
+        bits.extend_from_bitslice(BitSlice::<u8, Lsb0>::from_element(byte));
+        bits.push(byte.count_ones() % 2 == 0);
+    }
+    bits
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct CompleteCollector<'a> {
     pub(crate) data: &'a BitSlice<u8, Lsb0>,
@@ -30,7 +90,7 @@ impl <'a> CompleteCollector <'a> {
         if data_len == 0 {return Err(FrameError::EmptyFrame)}
         match data_len.cmp(&8) {
             Ordering::Greater => {
-                if data_len % 9 == 0 {
+                if data_len.is_multiple_of(9) {
                     let total_bytes = data_len / 9;
                     let mut out: Vec<u8> = Vec::with_capacity(total_bytes);
                     for byte_number in 0..total_bytes {
@@ -85,6 +145,33 @@ pub enum FrameAttributed {
     Miller(Frame),
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimedFrameAttributed {
+    Manchester(TimedFrame),
+    Miller(TimedFrame),
+}
+
+impl TimedFrameAttributed {
+    pub fn timed_frame(&self) -> &TimedFrame {
+        match self {
+            TimedFrameAttributed::Manchester(timed_frame) => timed_frame,
+            TimedFrameAttributed::Miller(timed_frame) => timed_frame,
+        }
+    }
+
+    /// Session from PCD frames and PICC frames decoded separately, in order
+    /// of start time. Both sets must share the same capture start.
+    pub fn merge(miller: Vec<TimedFrame>, manchester: Vec<TimedFrame>) -> Vec<Self> {
+        let mut session: Vec<Self> = miller
+            .into_iter()
+            .map(TimedFrameAttributed::Miller)
+            .chain(manchester.into_iter().map(TimedFrameAttributed::Manchester))
+            .collect();
+        session.sort_by_key(|timed_frame_attributed| timed_frame_attributed.timed_frame().start);
+        session
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
@@ -126,4 +213,72 @@ mod tests {
         let frame = complete_collector.to_frame().unwrap();
         assert_eq!(frame, Frame::Standard(vec![0x50, 0x00]));
     }
+
+    fn round_trip(frame: Frame) {
+        let bits = frame.to_bits();
+        let complete_collector = CompleteCollector { data: &bits };
+        assert_eq!(complete_collector.to_frame().unwrap(), frame);
+    }
+
+    #[test]
+    fn to_bits_1() {
+        assert_eq!(
+            Frame::Short(0x26).to_bits(),
+            bitvec![u8, Lsb0; 0, 1, 1, 0, 0, 1, 0]
+        );
+        round_trip(Frame::Short(0x26));
+        round_trip(Frame::Short(0x52));
+    }
+
+    #[test]
+    fn to_bits_2() {
+        assert_eq!(
+            Frame::Standard(vec![0x50, 0x00]).to_bits(),
+            bitvec![u8, Lsb0; 0, 0, 0, 0, 1, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 1, 0, 1, 0, 0, 1, 0, 1, 1, 0, 0, 1, 1, 0]
+        );
+        round_trip(Frame::Standard(vec![0x50, 0x00]));
+        round_trip(Frame::Standard(vec![0xB2]));
+        round_trip(Frame::Standard(vec![0x93, 0x70, 0x01, 0x02, 0x03, 0x04, 0x04]));
+    }
+
+    #[test]
+    fn to_bits_3() {
+        assert_eq!(
+            Frame::SddCleanCut(vec![0x50]).to_bits(),
+            bitvec![u8, Lsb0; 0, 0, 0, 0, 1, 0, 1, 0, 1]
+        );
+        round_trip(Frame::SddCleanCut(vec![0x50]));
+        round_trip(Frame::SddCleanCut(vec![0x93, 0x20]));
+        round_trip(Frame::SddCleanCut(vec![0x88, 0x04, 0x21, 0x32, 0x8F]));
+    }
+
+    #[test]
+    fn merge() {
+        let timed = |frame: Frame, start: u64| TimedFrame {
+            frame,
+            start,
+            end: start + 100,
+        };
+        let session = TimedFrameAttributed::merge(
+            vec![
+                timed(Frame::Short(0x26), 0),
+                timed(Frame::SddCleanCut(vec![0x93, 0x20]), 2000),
+            ],
+            vec![timed(Frame::SddCleanCut(vec![0x04, 0x00]), 1000)],
+        );
+        assert_eq!(
+            session,
+            vec![
+                TimedFrameAttributed::Miller(timed(Frame::Short(0x26), 0)),
+                TimedFrameAttributed::Manchester(timed(Frame::SddCleanCut(vec![0x04, 0x00]), 1000)),
+                TimedFrameAttributed::Miller(timed(Frame::SddCleanCut(vec![0x93, 0x20]), 2000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn to_bits_4() {
+        round_trip(Frame::SddBits(bitvec![u8, Lsb0; 1, 0, 0, 1, 0, 0, 1, 1]));
+        round_trip(Frame::SddBits(bitvec![u8, Lsb0; 1, 0, 0, 1, 0, 0, 1, 1, 0, 1, 1]));
+    }
 }
@@ -0,0 +1,641 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use bitvec::prelude::{BitSlice, BitVec, Lsb0};
+
+use crate::frame::{Frame, FrameAttributed};
+
+pub const REQA: u8 = 0x26;
+pub const WUPA: u8 = 0x52;
+pub const RATS: u8 = 0xE0;
+/// First byte of HLTA, followed by 0x00.
+pub const HLTA: u8 = 0x50;
+
+/// NVB of SELECT command, all 7 bytes are sent.
+pub const NVB_SELECT: u8 = 0x70;
+
+/// Cascade tag, first UID byte in cascade levels that are not the last.
+pub const CASCADE_TAG: u8 = 0x88;
+
+/// SAK bit set if UID is not complete.
+pub const SAK_CASCADE_BIT: u8 = 0x04;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum CascadeLevel {
+    One,
+    Two,
+    Three,
+}
+
+impl CascadeLevel {
+    pub fn from_sel(sel: u8) -> Option<Self> {
+        match sel {
+            0x93 => Some(CascadeLevel::One),
+            0x95 => Some(CascadeLevel::Two),
+            0x97 => Some(CascadeLevel::Three),
+            _ => None,
+        }
+    }
+
+    pub fn sel(&self) -> u8 {
+        match self {
+            CascadeLevel::One => 0x93,
+            CascadeLevel::Two => 0x95,
+            CascadeLevel::Three => 0x97,
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            CascadeLevel::One => 0,
+            CascadeLevel::Two => 1,
+            CascadeLevel::Three => 2,
+        }
+    }
+}
+
+/// Number of valid bits, second byte of ANTICOLLISION and SELECT commands.
+///
+/// Counts include SEL and NVB bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Nvb {
+    pub bytes: u8,
+    pub bits: u8,
+}
+
+impl Nvb {
+    pub fn from_byte(nvb: u8) -> Self {
+        Self {
+            bytes: nvb >> 4,
+            bits: nvb & 0x0F,
+        }
+    }
+
+    pub fn to_byte(&self) -> u8 {
+        (self.bytes << 4) | (self.bits & 0x0F)
+    }
+
+    /// Number of UID bits sent by PCD.
+    pub fn uid_bits(&self) -> usize {
+        (self.bytes as usize).saturating_sub(2) * 8 + self.bits as usize
+    }
+}
+
+/// Byte from 8 data bits followed by odd parity bit, `None` if parity is wrong.
+fn byte_with_parity(bits: &BitSlice<u8, Lsb0>) -> Option<u8> {
+    let byte = byte_from_bits(&bits[..8]);
+    if bits[8] == (byte.count_ones().is_multiple_of(2)) {
+        Some(byte)
+    } else {
+        None
+    }
+}
+
+/// Byte from up to 8 bits, least significant bit first.
+fn byte_from_bits(bits: &BitSlice<u8, Lsb0>) -> u8 {
+    bits.iter().enumerate().fold(
+        0,
+        |byte, (i, bit)| if *bit { byte | (1 << i) } else { byte },
+    )
+}
+
+/// Frame sent by PCD, as defined in ISO 14443-3.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PcdCommand {
+    Reqa,
+    Wupa,
+    Anticollision {
+        level: CascadeLevel,
+        nvb: Nvb,
+        /// UID bytes sent by PCD, the last one possibly incomplete.
+        uid_bits: Vec<u8>,
+    },
+    Select {
+        level: CascadeLevel,
+        uid: [u8; 4],
+        bcc: u8,
+    },
+    Hlta,
+    Rats {
+        fsdi: u8,
+        cid: u8,
+    },
+    Unknown(Frame),
+}
+
+impl PcdCommand {
+    pub fn from_frame(frame: &Frame) -> Self {
+        match frame {
+            Frame::Short(REQA) => PcdCommand::Reqa,
+            Frame::Short(WUPA) => PcdCommand::Wupa,
+            Frame::SddCleanCut(data) => {
+                if let [sel, nvb, uid_bits @ ..] = data.as_slice() {
+                    if let Some(level) = CascadeLevel::from_sel(*sel) {
+                        let nvb = Nvb::from_byte(*nvb);
+                        if (nvb.to_byte() != NVB_SELECT)
+                            & (nvb.uid_bits().div_ceil(8) == uid_bits.len())
+                        {
+                            return PcdCommand::Anticollision {
+                                level,
+                                nvb,
+                                uid_bits: uid_bits.to_vec(),
+                            };
+                        }
+                    }
+                }
+                PcdCommand::Unknown(frame.clone())
+            }
+            Frame::Standard(data) => match data.as_slice() {
+                [sel, NVB_SELECT, uid0, uid1, uid2, uid3, bcc] => {
+                    match CascadeLevel::from_sel(*sel) {
+                        Some(level) => PcdCommand::Select {
+                            level,
+                            uid: [*uid0, *uid1, *uid2, *uid3],
+                            bcc: *bcc,
+                        },
+                        None => PcdCommand::Unknown(frame.clone()),
+                    }
+                }
+                [HLTA, 0x00] => PcdCommand::Hlta,
+                [RATS, param] => PcdCommand::Rats {
+                    fsdi: param >> 4,
+                    cid: param & 0x0F,
+                },
+                _ => PcdCommand::Unknown(frame.clone()),
+            },
+            Frame::SddBits(bits) => PcdCommand::from_sdd_bits(bits)
+                .unwrap_or_else(|| PcdCommand::Unknown(frame.clone())),
+            _ => PcdCommand::Unknown(frame.clone()),
+        }
+    }
+
+    /// Bit-oriented ANTICOLLISION: SEL, NVB and full UID bytes go with parity
+    /// bits, the last `nvb.bits` UID bits go with no parity bit.
+    fn from_sdd_bits(bits: &BitSlice<u8, Lsb0>) -> Option<Self> {
+        if bits.len() < 18 {
+            return None;
+        }
+        let level = CascadeLevel::from_sel(byte_with_parity(&bits[..9])?)?;
+        let nvb = Nvb::from_byte(byte_with_parity(&bits[9..18])?);
+        if (nvb.bytes < 2)
+            | (nvb.bits > 7)
+            | (nvb.uid_bits() >= 40)
+            | (bits.len() != nvb.bytes as usize * 9 + nvb.bits as usize)
+        {
+            return None;
+        }
+        let full_bytes_end = nvb.bytes as usize * 9;
+        let mut uid_bits: Vec<u8> = Vec::with_capacity(nvb.uid_bits().div_ceil(8));
+        for byte_bits in bits[18..full_bytes_end].chunks(9) {
+            uid_bits.push(byte_with_parity(byte_bits)?);
+        }
+        if nvb.bits != 0 {
+            uid_bits.push(byte_from_bits(&bits[full_bytes_end..]));
+        }
+        Some(PcdCommand::Anticollision {
+            level,
+            nvb,
+            uid_bits,
+        })
+    }
+}
+
+/// Frame sent by PICC, as defined in ISO 14443-3.
+///
+/// Responses are recognized by the frame layout alone: ATQA is 2 bytes with
+/// no CRC, UID is 4 bytes and BCC with no CRC, ATS starts with its own length
+/// and has CRC. Single byte with CRC is SAK only as an answer to SELECT, see
+/// [`PiccResponse::from_frame_after`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PiccResponse {
+    Atqa([u8; 2]),
+    Uid { uid: [u8; 4], bcc: u8 },
+    Sak(u8),
+    Ats(Vec<u8>),
+    /// Single byte with CRC, not known to answer SELECT.
+    SingleByte(u8),
+    Unknown(Frame),
+}
+
+impl PiccResponse {
+    pub fn from_frame(frame: &Frame) -> Self {
+        match frame {
+            Frame::SddCleanCut(data) => match data.as_slice() {
+                [atqa0, atqa1] => PiccResponse::Atqa([*atqa0, *atqa1]),
+                [uid0, uid1, uid2, uid3, bcc] => PiccResponse::Uid {
+                    uid: [*uid0, *uid1, *uid2, *uid3],
+                    bcc: *bcc,
+                },
+                _ => PiccResponse::Unknown(frame.clone()),
+            },
+            Frame::Standard(data) => match data.as_slice() {
+                [byte] => PiccResponse::SingleByte(*byte),
+                [tl, ..] if *tl as usize == data.len() => PiccResponse::Ats(data.to_vec()),
+                _ => PiccResponse::Unknown(frame.clone()),
+            },
+            _ => PiccResponse::Unknown(frame.clone()),
+        }
+    }
+
+    /// Frame sent by PICC, with the preceding PCD command known.
+    ///
+    /// Answer to ANTICOLLISION is merged with the UID bits sent by PCD into
+    /// complete UID and BCC, single byte answer to SELECT is SAK. Frames that
+    /// do not fit are classified with
+    /// [`PiccResponse::from_frame`].
+    pub fn from_frame_after(frame: &Frame, command: &PcdCommand) -> Self {
+        match command {
+            PcdCommand::Anticollision { nvb, uid_bits, .. } => {
+                Self::from_sdd_answer(frame, *nvb, uid_bits)
+                    .unwrap_or_else(|| PiccResponse::from_frame(frame))
+            }
+            PcdCommand::Select { .. } => match frame {
+                Frame::Standard(data) if data.len() == 1 => PiccResponse::Sak(data[0]),
+                _ => PiccResponse::from_frame(frame),
+            },
+            _ => PiccResponse::from_frame(frame),
+        }
+    }
+
+    /// PICC completes the split byte first: remaining bits of the byte, then
+    /// the parity bit for the whole byte, then the rest of UID bytes and BCC,
+    /// each with parity bit.
+    fn from_sdd_answer(frame: &Frame, nvb: Nvb, uid_bits: &[u8]) -> Option<Self> {
+        if (nvb.bytes < 2) | (nvb.bits > 7) | (nvb.uid_bits() >= 40) {
+            return None;
+        }
+        let split_byte = nvb.bytes as usize - 2;
+        let known_bits = nvb.bits as usize;
+        if uid_bits.len() != nvb.uid_bits().div_ceil(8) {
+            return None;
+        }
+        let expected_len = 9 - known_bits + 9 * (4 - split_byte);
+        let bits = match frame {
+            Frame::Short(byte) if expected_len < 8 => {
+                let mut bits = BitVec::<u8, Lsb0>::from_element(*byte);
+                bits.truncate(expected_len);
+                bits
+            }
+            Frame::Short(_) => return None,
+            _ => frame.to_bits(),
+        };
+        if bits.len() != expected_len {
+            return None;
+        }
+        let mut uid_bcc = [0u8; 5];
+        uid_bcc[..split_byte].copy_from_slice(&uid_bits[..split_byte]);
+        let known = if known_bits == 0 {
+            0
+        } else {
+            uid_bits[split_byte] & ((1 << known_bits) - 1)
+        };
+        uid_bcc[split_byte] = known | (byte_from_bits(&bits[..8 - known_bits]) << known_bits);
+        if bits[8 - known_bits] != (uid_bcc[split_byte].count_ones().is_multiple_of(2)) {
+            return None;
+        }
+        for (i, byte_bits) in bits[9 - known_bits..].chunks(9).enumerate() {
+            uid_bcc[split_byte + 1 + i] = byte_with_parity(byte_bits)?;
+        }
+        Some(PiccResponse::Uid {
+            uid: [uid_bcc[0], uid_bcc[1], uid_bcc[2], uid_bcc[3]],
+            bcc: uid_bcc[4],
+        })
+    }
+}
+
+/// BCC, exclusive-or of all UID bytes in cascade level.
+pub fn bcc(uid: &[u8; 4]) -> u8 {
+    uid[0] ^ uid[1] ^ uid[2] ^ uid[3]
+}
+
+/// UID collected from a session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssembledUid {
+    /// UID bytes with cascade tags removed, 4, 7 or 10 bytes if complete.
+    pub uid: Vec<u8>,
+    /// Cascade levels with BCC not matching the UID bytes.
+    pub bcc_mismatch: Vec<CascadeLevel>,
+    /// SAK without cascade bit was received for the last cascade level.
+    pub complete: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct CascadeLevelRecord {
+    uid_bcc: Option<([u8; 4], u8)>,
+    sak: Option<u8>,
+}
+
+/// Collects UID bytes from SELECT commands, UID answers to ANTICOLLISION
+/// and SAK answers to SELECT, for all cascade levels.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UidAssembler {
+    levels: [CascadeLevelRecord; 3],
+    /// Last ANTICOLLISION or SELECT sent by PCD.
+    last_command: Option<PcdCommand>,
+}
+
+impl UidAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_command(&mut self, command: &PcdCommand) {
+        match command {
+            PcdCommand::Anticollision { .. } => self.last_command = Some(command.clone()),
+            PcdCommand::Select { level, uid, bcc } => {
+                self.levels[level.index()].uid_bcc = Some((*uid, *bcc));
+                self.last_command = Some(command.clone());
+            }
+            _ => self.last_command = None,
+        }
+    }
+
+    pub fn add_response(&mut self, response: &PiccResponse) {
+        match (response, &self.last_command) {
+            (PiccResponse::Uid { uid, bcc }, Some(PcdCommand::Anticollision { level, .. })) => {
+                self.levels[level.index()].uid_bcc = Some((*uid, *bcc))
+            }
+            (PiccResponse::Sak(sak), Some(PcdCommand::Select { level, .. })) => {
+                self.levels[level.index()].sak = Some(*sak)
+            }
+            _ => {}
+        }
+        self.last_command = None;
+    }
+
+    pub fn add_frame(&mut self, frame: &FrameAttributed) {
+        match frame {
+            FrameAttributed::Miller(frame) => self.add_command(&PcdCommand::from_frame(frame)),
+            FrameAttributed::Manchester(frame) => {
+                let response = match &self.last_command {
+                    Some(command) => PiccResponse::from_frame_after(frame, command),
+                    None => PiccResponse::from_frame(frame),
+                };
+                self.add_response(&response)
+            }
+        }
+    }
+
+    /// UID known so far.
+    ///
+    /// Cascade goes on to the next level if SAK has cascade bit set or, with
+    /// no SAK recorded, if UID bytes start with cascade tag.
+    pub fn uid(&self) -> AssembledUid {
+        let mut assembled_uid = AssembledUid {
+            uid: Vec::new(),
+            bcc_mismatch: Vec::new(),
+            complete: false,
+        };
+        for level in [CascadeLevel::One, CascadeLevel::Two, CascadeLevel::Three] {
+            let record = self.levels[level.index()];
+            let Some((uid, bcc_received)) = record.uid_bcc else {
+                break;
+            };
+            if bcc(&uid) != bcc_received {
+                assembled_uid.bcc_mismatch.push(level);
+            }
+            let cascade = match record.sak {
+                Some(sak) => sak & SAK_CASCADE_BIT != 0,
+                None => (uid[0] == CASCADE_TAG) & (level != CascadeLevel::Three),
+            };
+            if cascade {
+                let start = if uid[0] == CASCADE_TAG { 1 } else { 0 };
+                assembled_uid.uid.extend_from_slice(&uid[start..]);
+            } else {
+                assembled_uid.uid.extend_from_slice(&uid);
+                assembled_uid.complete = record.sak.is_some();
+                break;
+            }
+        }
+        assembled_uid
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manchester::ManchesterElementSet;
+    use crate::miller_reworked::MillerElementSet;
+
+    #[test]
+    fn pcd_commands() {
+        assert_eq!(
+            PcdCommand::from_frame(&Frame::Short(0x26)),
+            PcdCommand::Reqa
+        );
+        assert_eq!(
+            PcdCommand::from_frame(&Frame::Short(0x52)),
+            PcdCommand::Wupa
+        );
+        assert_eq!(
+            PcdCommand::from_frame(&Frame::Standard(vec![0x50, 0x00])),
+            PcdCommand::Hlta
+        );
+        assert_eq!(
+            PcdCommand::from_frame(&Frame::SddCleanCut(vec![0x93, 0x20])),
+            PcdCommand::Anticollision {
+                level: CascadeLevel::One,
+                nvb: Nvb { bytes: 2, bits: 0 },
+                uid_bits: vec![],
+            }
+        );
+        assert_eq!(
+            PcdCommand::from_frame(&Frame::Standard(vec![
+                0x95, 0x70, 0x01, 0x02, 0x03, 0x04, 0x04
+            ])),
+            PcdCommand::Select {
+                level: CascadeLevel::Two,
+                uid: [0x01, 0x02, 0x03, 0x04],
+                bcc: 0x04,
+            }
+        );
+        assert_eq!(
+            PcdCommand::from_frame(&Frame::Standard(vec![0xE0, 0x80])),
+            PcdCommand::Rats { fsdi: 8, cid: 0 }
+        );
+        assert_eq!(
+            PcdCommand::from_frame(&Frame::Standard(vec![0xB2])),
+            PcdCommand::Unknown(Frame::Standard(vec![0xB2]))
+        );
+    }
+
+    #[test]
+    fn uid_single() {
+        let session = [
+            FrameAttributed::Miller(Frame::Short(0x26)),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x04, 0x00])),
+            FrameAttributed::Miller(Frame::SddCleanCut(vec![0x93, 0x20])),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0xDE, 0xAD, 0xBE, 0xEF, 0x22])),
+            FrameAttributed::Miller(Frame::Standard(vec![
+                0x93, 0x70, 0xDE, 0xAD, 0xBE, 0xEF, 0x22,
+            ])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x08])),
+        ];
+        let mut uid_assembler = UidAssembler::new();
+        for frame in session.iter() {
+            uid_assembler.add_frame(frame);
+        }
+        assert_eq!(
+            uid_assembler.uid(),
+            AssembledUid {
+                uid: vec![0xDE, 0xAD, 0xBE, 0xEF],
+                bcc_mismatch: vec![],
+                complete: true,
+            }
+        );
+    }
+
+    #[test]
+    fn uid_double() {
+        let session = [
+            FrameAttributed::Miller(Frame::Short(0x52)),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x44, 0x00])),
+            FrameAttributed::Miller(Frame::SddCleanCut(vec![0x93, 0x20])),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x88, 0x04, 0x21, 0x32, 0x9F])),
+            FrameAttributed::Miller(Frame::Standard(vec![
+                0x93, 0x70, 0x88, 0x04, 0x21, 0x32, 0x9F,
+            ])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x04])),
+            FrameAttributed::Miller(Frame::SddCleanCut(vec![0x95, 0x20])),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x4A, 0x5B, 0x6C, 0x80, 0xFD])),
+            FrameAttributed::Miller(Frame::Standard(vec![
+                0x95, 0x70, 0x4A, 0x5B, 0x6C, 0x80, 0xFD,
+            ])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x00])),
+        ];
+        let mut uid_assembler = UidAssembler::new();
+        for frame in session.iter() {
+            uid_assembler.add_frame(frame);
+        }
+        assert_eq!(
+            uid_assembler.uid(),
+            AssembledUid {
+                uid: vec![0x04, 0x21, 0x32, 0x4A, 0x5B, 0x6C, 0x80],
+                bcc_mismatch: vec![],
+                complete: true,
+            }
+        );
+    }
+
+    #[test]
+    fn uid_incomplete_bad_bcc() {
+        let mut uid_assembler = UidAssembler::new();
+        uid_assembler.add_command(&PcdCommand::Select {
+            level: CascadeLevel::One,
+            uid: [0x88, 0x04, 0x21, 0x32],
+            bcc: 0x00,
+        });
+        uid_assembler.add_response(&PiccResponse::Sak(0x04));
+        assert_eq!(
+            uid_assembler.uid(),
+            AssembledUid {
+                uid: vec![0x04, 0x21, 0x32],
+                bcc_mismatch: vec![CascadeLevel::One],
+                complete: false,
+            }
+        );
+    }
+
+    #[test]
+    fn uid_split_byte() {
+        // PCD knows 12 UID bits: SEL, NVB 0x34, one full byte and 4 more bits.
+        let cl_bits = Frame::SddCleanCut(vec![0xDE, 0xAD, 0xBE, 0xEF, 0x22]).to_bits();
+        let mut pcd_bits = Frame::SddCleanCut(vec![0x93, 0x34]).to_bits();
+        pcd_bits.extend_from_bitslice(&cl_bits[..13]);
+        let pcd_frame = MillerElementSet::from_bits(&pcd_bits)
+            .collect_frame()
+            .unwrap();
+        let command = PcdCommand::from_frame(&pcd_frame);
+        assert_eq!(
+            command,
+            PcdCommand::Anticollision {
+                level: CascadeLevel::One,
+                nvb: Nvb { bytes: 3, bits: 4 },
+                uid_bits: vec![0xDE, 0x0D],
+            }
+        );
+
+        // PICC sends the remaining 4 bits of the split byte, then its parity.
+        let picc_frame = ManchesterElementSet::from_bits(&cl_bits[13..])
+            .collect_frame()
+            .unwrap();
+        assert!(matches!(picc_frame, Frame::SddBits(_)));
+        assert_eq!(
+            PiccResponse::from_frame_after(&picc_frame, &command),
+            PiccResponse::Uid {
+                uid: [0xDE, 0xAD, 0xBE, 0xEF],
+                bcc: 0x22,
+            }
+        );
+
+        let mut uid_assembler = UidAssembler::new();
+        uid_assembler.add_frame(&FrameAttributed::Miller(pcd_frame));
+        uid_assembler.add_frame(&FrameAttributed::Manchester(picc_frame));
+        assert_eq!(uid_assembler.uid().uid, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn uid_split_bcc() {
+        // Only 2 bits of BCC are left for PICC, sent as short frame.
+        let command = PcdCommand::Anticollision {
+            level: CascadeLevel::One,
+            nvb: Nvb { bytes: 6, bits: 6 },
+            uid_bits: vec![0xDE, 0xAD, 0xBE, 0xEF, 0x22],
+        };
+        let cl_bits = Frame::SddCleanCut(vec![0xDE, 0xAD, 0xBE, 0xEF, 0x22]).to_bits();
+        let frame = Frame::Short(byte_from_bits(&cl_bits[42..]));
+        assert_eq!(
+            PiccResponse::from_frame_after(&frame, &command),
+            PiccResponse::Uid {
+                uid: [0xDE, 0xAD, 0xBE, 0xEF],
+                bcc: 0x22,
+            }
+        );
+        let wrong_parity = Frame::Short(byte_from_bits(&cl_bits[42..]) ^ 0b100);
+        assert_eq!(
+            PiccResponse::from_frame_after(&wrong_parity, &command),
+            PiccResponse::Unknown(wrong_parity.clone())
+        );
+    }
+
+    #[test]
+    fn picc_responses() {
+        assert_eq!(
+            PiccResponse::from_frame(&Frame::SddCleanCut(vec![0x04, 0x00])),
+            PiccResponse::Atqa([0x04, 0x00])
+        );
+        assert_eq!(
+            PiccResponse::from_frame(&Frame::SddCleanCut(vec![0x01, 0x02, 0x03, 0x04, 0x04])),
+            PiccResponse::Uid {
+                uid: [0x01, 0x02, 0x03, 0x04],
+                bcc: 0x04,
+            }
+        );
+        assert_eq!(
+            PiccResponse::from_frame(&Frame::Standard(vec![0x08])),
+            PiccResponse::SingleByte(0x08)
+        );
+        assert_eq!(
+            PiccResponse::from_frame_after(
+                &Frame::Standard(vec![0x08]),
+                &PcdCommand::Select {
+                    level: CascadeLevel::One,
+                    uid: [0x01, 0x02, 0x03, 0x04],
+                    bcc: 0x04,
+                }
+            ),
+            PiccResponse::Sak(0x08)
+        );
+        assert_eq!(
+            PiccResponse::from_frame(&Frame::Standard(vec![0x05, 0x78, 0x80, 0x70, 0x02])),
+            PiccResponse::Ats(vec![0x05, 0x78, 0x80, 0x70, 0x02])
+        );
+        assert_eq!(
+            PiccResponse::from_frame(&Frame::Standard(vec![0x0A, 0x00])),
+            PiccResponse::Unknown(Frame::Standard(vec![0x0A, 0x00]))
+        );
+    }
+}
@@ -0,0 +1,781 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::config::DecoderConfig;
+use crate::error::IsoDepError;
+use crate::frame::{Frame, FrameAttributed};
+use crate::iso14443_3::{RATS, REQA, WUPA};
+
+/// PCB bit set if CID byte follows PCB.
+pub const PCB_CID: u8 = 0x08;
+
+/// PCB bit set in I-block if NAD byte follows PCB, or CID if present.
+pub const PCB_NAD: u8 = 0x04;
+
+/// PCB bit set in I-block if chaining is used.
+pub const PCB_CHAINING: u8 = 0x10;
+
+/// PCB bit set in R-block for NAK.
+pub const PCB_NAK: u8 = 0x10;
+
+/// Block number bit in I-block and R-block PCB.
+pub const PCB_BLOCK_NUMBER: u8 = 0x01;
+
+/// PPSS byte with CID bits cleared, starts PPS request and PPS response.
+pub const PPSS: u8 = 0xD0;
+
+/// PPS0 with PPS1 following.
+pub const PPS0_PPS1: u8 = 0x11;
+
+/// FSC for FSCI values, in bytes; FSCI above 0x0C is RFU.
+const FSC_TABLE: [u16; 13] = [16, 24, 32, 40, 48, 64, 96, 128, 256, 512, 1024, 2048, 4096];
+
+/// Carrier cycles in FWT and SFGT for FWI and SFGI equal to 0.
+const FWT_BASE_FC: u32 = 256 * 16;
+
+/// Transmission bit rate.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum BitRate {
+    Kbps106,
+    Kbps212,
+    Kbps424,
+    Kbps848,
+}
+
+impl BitRate {
+    /// Bit rate for DSI or DRI value in PPS.
+    pub fn from_divisor_integer(di: u8) -> Self {
+        match di & 0x03 {
+            0 => BitRate::Kbps106,
+            1 => BitRate::Kbps212,
+            2 => BitRate::Kbps424,
+            _ => BitRate::Kbps848,
+        }
+    }
+
+    /// Divisor D, bit period is 128/(D·fc).
+    pub fn divisor(&self) -> u16 {
+        match self {
+            BitRate::Kbps106 => 1,
+            BitRate::Kbps212 => 2,
+            BitRate::Kbps424 => 4,
+            BitRate::Kbps848 => 8,
+        }
+    }
+
+    /// Decoder config for this bit rate, from config at 106 kbit/s.
+    ///
+    /// Bit is 8 ticks at any bit rate, so only tick length changes; tolerance,
+    /// idle gaps and pause bounds scale together with it. Tick length is kept
+    /// exact with `tick_len_divisor`.
+    ///
+    /// Only PCD to PICC direction is Modified Miller at all bit rates. PICC
+    /// to PCD above 106 kbit/s is BPSK and could not be decoded with
+    /// Manchester decoder.
+    pub fn config(&self, config_106: &DecoderConfig) -> DecoderConfig {
+        DecoderConfig {
+            tick_len_divisor: config_106.tick_len_divisor * self.divisor(),
+            ..*config_106
+        }
+    }
+}
+
+/// Block content, by block type coded in PCB.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BlockKind {
+    I {
+        block_number: u8,
+        chaining: bool,
+        inf: Vec<u8>,
+    },
+    RAck {
+        block_number: u8,
+    },
+    RNak {
+        block_number: u8,
+    },
+    SDeselect,
+    /// S(WTX) with WTXM byte, power level indication bits cleared.
+    SWtx(u8),
+}
+
+/// ISO 14443-4 block, as defined in ISO 14443-4.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block {
+    pub cid: Option<u8>,
+    pub nad: Option<u8>,
+    pub kind: BlockKind,
+}
+
+impl Block {
+    /// Block from frame data, with no CRC_A.
+    ///
+    /// CID and WTXM are given without power level indication bits.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, IsoDepError> {
+        let (pcb, mut rest) = data.split_first().ok_or(IsoDepError::EmptyBlock)?;
+        let mut take_byte = |present: bool| -> Result<Option<u8>, IsoDepError> {
+            if present {
+                let (byte, after) = rest.split_first().ok_or(IsoDepError::Truncated)?;
+                rest = after;
+                Ok(Some(*byte))
+            } else {
+                Ok(None)
+            }
+        };
+        let block_number = pcb & PCB_BLOCK_NUMBER;
+        let block = if pcb & 0xE2 == 0x02 {
+            let cid = take_byte(pcb & PCB_CID != 0)?.map(|cid| cid & 0x0F);
+            let nad = take_byte(pcb & PCB_NAD != 0)?;
+            Block {
+                cid,
+                nad,
+                kind: BlockKind::I {
+                    block_number,
+                    chaining: pcb & PCB_CHAINING != 0,
+                    inf: rest.to_vec(),
+                },
+            }
+        } else if pcb & 0xE6 == 0xA2 {
+            let cid = take_byte(pcb & PCB_CID != 0)?.map(|cid| cid & 0x0F);
+            if !rest.is_empty() {
+                return Err(IsoDepError::UnexpectedInf);
+            }
+            let kind = if pcb & PCB_NAK != 0 {
+                BlockKind::RNak { block_number }
+            } else {
+                BlockKind::RAck { block_number }
+            };
+            Block {
+                cid,
+                nad: None,
+                kind,
+            }
+        } else if pcb & 0xC7 == 0xC2 {
+            let cid = take_byte(pcb & PCB_CID != 0)?.map(|cid| cid & 0x0F);
+            let kind = match (pcb & 0x30, rest) {
+                (0x00, []) => BlockKind::SDeselect,
+                (0x30, [wtxm]) => BlockKind::SWtx(wtxm & 0x3F),
+                (0x00, _) => return Err(IsoDepError::UnexpectedInf),
+                (0x30, []) => return Err(IsoDepError::Truncated),
+                _ => return Err(IsoDepError::UnknownPcb(*pcb)),
+            };
+            Block {
+                cid,
+                nad: None,
+                kind,
+            }
+        } else {
+            return Err(IsoDepError::UnknownPcb(*pcb));
+        };
+        Ok(block)
+    }
+
+    pub fn from_frame(frame: &Frame) -> Result<Self, IsoDepError> {
+        match frame {
+            Frame::Standard(data) => Self::from_bytes(data),
+            _ => Err(IsoDepError::NotStandardFrame),
+        }
+    }
+}
+
+/// Bit rates supported by PICC, coded in TA(1) interface byte.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AtsBitRates {
+    /// Same bit rate required in both directions.
+    pub same_d_only: bool,
+    /// Supported PICC to PCD bit rates above 106 kbit/s.
+    pub picc_to_pcd: Vec<BitRate>,
+    /// Supported PCD to PICC bit rates above 106 kbit/s.
+    pub pcd_to_picc: Vec<BitRate>,
+}
+
+impl AtsBitRates {
+    pub fn from_byte(ta: u8) -> Self {
+        let rates = |bits: u8| -> Vec<BitRate> {
+            [BitRate::Kbps212, BitRate::Kbps424, BitRate::Kbps848]
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| bits & (1 << i) != 0)
+                .map(|(_, bit_rate)| bit_rate)
+                .collect()
+        };
+        Self {
+            same_d_only: ta & 0x80 != 0,
+            picc_to_pcd: rates((ta >> 4) & 0x07),
+            pcd_to_picc: rates(ta & 0x07),
+        }
+    }
+}
+
+/// Answer to RATS, as defined in ISO 14443-4.
+///
+/// Interface bytes that are not sent get their default values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ats {
+    pub fsci: u8,
+    /// Interface byte TA(1), if sent.
+    pub ta: Option<u8>,
+    /// Interface byte TB(1), if sent.
+    pub tb: Option<u8>,
+    /// Interface byte TC(1), if sent.
+    pub tc: Option<u8>,
+    pub fwi: u8,
+    pub sfgi: u8,
+    pub nad_supported: bool,
+    pub cid_supported: bool,
+    pub historical_bytes: Vec<u8>,
+}
+
+impl Ats {
+    /// ATS from frame data, starting with TL, with no CRC_A.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, IsoDepError> {
+        let (tl, rest) = data.split_first().ok_or(IsoDepError::EmptyBlock)?;
+        if *tl as usize != data.len() {
+            return Err(IsoDepError::AtsLength);
+        }
+        let mut ats = Ats {
+            fsci: 2,
+            ta: None,
+            tb: None,
+            tc: None,
+            fwi: 4,
+            sfgi: 0,
+            nad_supported: false,
+            cid_supported: true,
+            historical_bytes: Vec::new(),
+        };
+        let Some((t0, mut rest)) = rest.split_first() else {
+            return Ok(ats);
+        };
+        ats.fsci = t0 & 0x0F;
+        let mut interface_byte = |mask: u8| -> Result<Option<u8>, IsoDepError> {
+            if t0 & mask != 0 {
+                let (byte, after) = rest.split_first().ok_or(IsoDepError::Truncated)?;
+                rest = after;
+                Ok(Some(*byte))
+            } else {
+                Ok(None)
+            }
+        };
+        ats.ta = interface_byte(0x10)?;
+        ats.tb = interface_byte(0x20)?;
+        ats.tc = interface_byte(0x40)?;
+        if let Some(tb) = ats.tb {
+            ats.fwi = tb >> 4;
+            ats.sfgi = tb & 0x0F;
+        }
+        if let Some(tc) = ats.tc {
+            ats.nad_supported = tc & 0x01 != 0;
+            ats.cid_supported = tc & 0x02 != 0;
+        }
+        ats.historical_bytes = rest.to_vec();
+        Ok(ats)
+    }
+
+    /// Maximum frame size PICC accepts, in bytes.
+    pub fn fsc(&self) -> Option<u16> {
+        FSC_TABLE.get(self.fsci as usize).copied()
+    }
+
+    /// Frame waiting time, in carrier cycles.
+    pub fn fwt_fc(&self) -> u32 {
+        FWT_BASE_FC << self.fwi.min(14)
+    }
+
+    /// Startup frame guard time, in carrier cycles; 0 if not required.
+    pub fn sfgt_fc(&self) -> u32 {
+        match self.sfgi {
+            0 | 15 => 0,
+            sfgi => FWT_BASE_FC << sfgi,
+        }
+    }
+
+    /// Bit rates from TA(1); only 106 kbit/s if TA(1) is not sent.
+    pub fn bit_rates(&self) -> AtsBitRates {
+        AtsBitRates::from_byte(self.ta.unwrap_or(0))
+    }
+}
+
+/// PPS request, as defined in ISO 14443-4.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Pps {
+    pub cid: u8,
+    /// Divisor integer for PICC to PCD direction.
+    pub dsi: u8,
+    /// Divisor integer for PCD to PICC direction.
+    pub dri: u8,
+}
+
+impl Pps {
+    /// PPS request, PPS1 not sent means no bit rate change.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        match data {
+            [ppss, pps0, rest @ ..] if ppss & 0xF0 == PPSS => {
+                let pps1 = match (pps0 & PPS0_PPS1 == PPS0_PPS1, rest) {
+                    (true, [pps1]) => *pps1,
+                    (false, []) => 0,
+                    _ => return None,
+                };
+                Some(Pps {
+                    cid: ppss & 0x0F,
+                    dsi: (pps1 >> 2) & 0x03,
+                    dri: pps1 & 0x03,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn picc_to_pcd(&self) -> BitRate {
+        BitRate::from_divisor_integer(self.dsi)
+    }
+
+    pub fn pcd_to_picc(&self) -> BitRate {
+        BitRate::from_divisor_integer(self.dri)
+    }
+}
+
+/// Follows bit rate changes through a session.
+///
+/// New bit rates apply after PPS response. REQA, WUPA, S(DESELECT) and its
+/// answer bring both directions back to 106 kbit/s.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitRateTracker {
+    pcd_to_picc: BitRate,
+    picc_to_pcd: BitRate,
+    pending: Option<Pps>,
+}
+
+impl BitRateTracker {
+    pub fn new() -> Self {
+        Self {
+            pcd_to_picc: BitRate::Kbps106,
+            picc_to_pcd: BitRate::Kbps106,
+            pending: None,
+        }
+    }
+
+    pub fn pcd_to_picc(&self) -> BitRate {
+        self.pcd_to_picc
+    }
+
+    /// PICC to PCD bit rate; above 106 kbit/s PICC answers are BPSK coded and
+    /// are not decoded in this crate.
+    pub fn picc_to_pcd(&self) -> BitRate {
+        self.picc_to_pcd
+    }
+
+    /// Back to 106 kbit/s in both directions.
+    ///
+    /// Use when frames fail to decode at the tracked bit rate, e.g. after
+    /// a missed S(DESELECT) or a PCD falling back to 106 kbit/s.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns `true` if bit rate in any direction has changed.
+    pub fn add_frame(&mut self, frame: &FrameAttributed) -> bool {
+        let before = (self.pcd_to_picc, self.picc_to_pcd);
+        match frame {
+            FrameAttributed::Miller(Frame::Short(REQA | WUPA)) => self.reset(),
+            FrameAttributed::Miller(Frame::Standard(data)) => {
+                self.pending = Pps::from_bytes(data);
+                if self.pending.is_none() & Self::is_deselect(data) {
+                    self.reset();
+                }
+            }
+            FrameAttributed::Miller(_) => self.pending = None,
+            FrameAttributed::Manchester(Frame::Standard(data)) => {
+                match (self.pending.take(), data.as_slice()) {
+                    (Some(pps), [ppss]) if *ppss == PPSS | pps.cid => {
+                        self.pcd_to_picc = pps.pcd_to_picc();
+                        self.picc_to_pcd = pps.picc_to_pcd();
+                    }
+                    _ => {
+                        if Self::is_deselect(data) {
+                            self.reset();
+                        }
+                    }
+                }
+            }
+            FrameAttributed::Manchester(_) => self.pending = None,
+        }
+        before != (self.pcd_to_picc, self.picc_to_pcd)
+    }
+
+    fn is_deselect(data: &[u8]) -> bool {
+        matches!(
+            Block::from_bytes(data),
+            Ok(Block {
+                kind: BlockKind::SDeselect,
+                ..
+            })
+        )
+    }
+}
+
+impl Default for BitRateTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Complete INF field, chained I-blocks joined together.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IsoDepPayload {
+    Command(Vec<u8>),
+    Response(Vec<u8>),
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct ChainRecord {
+    inf: Vec<u8>,
+    last_block_number: Option<u8>,
+}
+
+impl ChainRecord {
+    /// Complete INF when the last block of the chain is added.
+    ///
+    /// I-block with the same block number as the previous one from the same
+    /// side is a retransmission, and is skipped.
+    fn add(&mut self, block_number: u8, chaining: bool, inf: &[u8]) -> Option<Vec<u8>> {
+        if self.last_block_number == Some(block_number) {
+            return None;
+        }
+        self.last_block_number = Some(block_number);
+        self.inf.extend_from_slice(inf);
+        if chaining {
+            None
+        } else {
+            Some(core::mem::take(&mut self.inf))
+        }
+    }
+}
+
+/// Collects INF fields of I-blocks, both from PCD and from PICC.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InfAssembler {
+    pcd: ChainRecord,
+    picc: ChainRecord,
+    /// RATS was sent, the next PICC frame is ATS and not a block.
+    ats_pending: bool,
+}
+
+impl InfAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget partial chains and block numbers, as after protocol
+    /// activation.
+    pub fn reset(&mut self) {
+        *self = Self::default()
+    }
+
+    pub fn add_command(&mut self, block: &Block) -> Option<IsoDepPayload> {
+        match &block.kind {
+            BlockKind::I {
+                block_number,
+                chaining,
+                inf,
+            } => self
+                .pcd
+                .add(*block_number, *chaining, inf)
+                .map(IsoDepPayload::Command),
+            _ => None,
+        }
+    }
+
+    pub fn add_response(&mut self, block: &Block) -> Option<IsoDepPayload> {
+        match &block.kind {
+            BlockKind::I {
+                block_number,
+                chaining,
+                inf,
+            } => self
+                .picc
+                .add(*block_number, *chaining, inf)
+                .map(IsoDepPayload::Response),
+            BlockKind::SDeselect => {
+                self.reset();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// RATS restarts block numbering, and PICC answer to it is ATS, not a
+    /// block; frames that are not ISO-DEP blocks are skipped.
+    pub fn add_frame(&mut self, frame: &FrameAttributed) -> Option<IsoDepPayload> {
+        match frame {
+            FrameAttributed::Miller(Frame::Standard(data))
+                if (data.len() == 2) & (data[0] == RATS) =>
+            {
+                self.reset();
+                self.ats_pending = true;
+                None
+            }
+            FrameAttributed::Miller(frame) => {
+                self.ats_pending = false;
+                Block::from_frame(frame)
+                    .ok()
+                    .and_then(|block| self.add_command(&block))
+            }
+            FrameAttributed::Manchester(_) if self.ats_pending => {
+                self.ats_pending = false;
+                None
+            }
+            FrameAttributed::Manchester(frame) => Block::from_frame(frame)
+                .ok()
+                .and_then(|block| self.add_response(&block)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miller_reworked::MillerElementSet;
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            Block::from_bytes(&[0x02, 0x00, 0xA4, 0x04, 0x00]),
+            Ok(Block {
+                cid: None,
+                nad: None,
+                kind: BlockKind::I {
+                    block_number: 0,
+                    chaining: false,
+                    inf: vec![0x00, 0xA4, 0x04, 0x00],
+                },
+            })
+        );
+        assert_eq!(
+            Block::from_bytes(&[0x1F, 0x41, 0x12, 0xAB]),
+            Ok(Block {
+                cid: Some(1),
+                nad: Some(0x12),
+                kind: BlockKind::I {
+                    block_number: 1,
+                    chaining: true,
+                    inf: vec![0xAB],
+                },
+            })
+        );
+        assert_eq!(
+            Block::from_bytes(&[0xA3]),
+            Ok(Block {
+                cid: None,
+                nad: None,
+                kind: BlockKind::RAck { block_number: 1 },
+            })
+        );
+        assert_eq!(
+            Block::from_bytes(&[0xBA, 0x02]),
+            Ok(Block {
+                cid: Some(2),
+                nad: None,
+                kind: BlockKind::RNak { block_number: 0 },
+            })
+        );
+        assert_eq!(
+            Block::from_bytes(&[0xC2]),
+            Ok(Block {
+                cid: None,
+                nad: None,
+                kind: BlockKind::SDeselect,
+            })
+        );
+        assert_eq!(
+            Block::from_bytes(&[0xF2, 0x81]),
+            Ok(Block {
+                cid: None,
+                nad: None,
+                kind: BlockKind::SWtx(0x01),
+            })
+        );
+    }
+
+    #[test]
+    fn blocks_broken() {
+        assert_eq!(Block::from_bytes(&[]), Err(IsoDepError::EmptyBlock));
+        assert_eq!(Block::from_bytes(&[0x0A]), Err(IsoDepError::Truncated));
+        assert_eq!(
+            Block::from_bytes(&[0xA2, 0x00]),
+            Err(IsoDepError::UnexpectedInf)
+        );
+        assert_eq!(Block::from_bytes(&[0xF2]), Err(IsoDepError::Truncated));
+        assert_eq!(
+            Block::from_bytes(&[0x26]),
+            Err(IsoDepError::UnknownPcb(0x26))
+        );
+        assert_eq!(
+            Block::from_frame(&Frame::SddCleanCut(vec![0x02, 0x00])),
+            Err(IsoDepError::NotStandardFrame)
+        );
+    }
+
+    #[test]
+    fn ats() {
+        let ats = Ats::from_bytes(&[
+            0x0B, 0x78, 0x77, 0x91, 0x02, 0x80, 0x31, 0x80, 0x66, 0xB0, 0x84,
+        ])
+        .unwrap();
+        assert_eq!(ats.fsci, 8);
+        assert_eq!(ats.fsc(), Some(256));
+        assert_eq!(ats.fwi, 9);
+        assert_eq!(ats.fwt_fc(), 4096 * 512);
+        assert_eq!(ats.sfgi, 1);
+        assert_eq!(ats.sfgt_fc(), 8192);
+        assert!(!ats.nad_supported);
+        assert!(ats.cid_supported);
+        assert_eq!(
+            ats.bit_rates(),
+            AtsBitRates {
+                same_d_only: false,
+                picc_to_pcd: vec![BitRate::Kbps212, BitRate::Kbps424, BitRate::Kbps848],
+                pcd_to_picc: vec![BitRate::Kbps212, BitRate::Kbps424, BitRate::Kbps848],
+            }
+        );
+        assert_eq!(
+            ats.historical_bytes,
+            vec![0x80, 0x31, 0x80, 0x66, 0xB0, 0x84]
+        );
+
+        let ats = Ats::from_bytes(&[0x05, 0x78, 0x80, 0x70, 0x02]).unwrap();
+        assert_eq!(ats.fsc(), Some(256));
+        assert!(ats.bit_rates().pcd_to_picc.is_empty());
+        assert_eq!(ats.fwi, 7);
+        assert!(ats.historical_bytes.is_empty());
+
+        let ats = Ats::from_bytes(&[0x01]).unwrap();
+        assert_eq!(ats.fsc(), Some(32));
+        assert_eq!(ats.fwi, 4);
+
+        assert_eq!(Ats::from_bytes(&[0x03, 0x70]), Err(IsoDepError::AtsLength));
+        assert_eq!(
+            Ats::from_bytes(&[0x03, 0x70, 0x00]),
+            Err(IsoDepError::Truncated)
+        );
+    }
+
+    #[test]
+    fn bit_rate_switch() {
+        let session = [
+            FrameAttributed::Miller(Frame::Standard(vec![0xE0, 0x80])),
+            FrameAttributed::Manchester(Frame::Standard(vec![
+                0x0B, 0x78, 0x77, 0x91, 0x02, 0x80, 0x31, 0x80, 0x66, 0xB0, 0x84,
+            ])),
+            FrameAttributed::Miller(Frame::Standard(vec![0xD0, 0x11, 0x0A])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0xD0])),
+            FrameAttributed::Miller(Frame::Standard(vec![0xC2])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0xC2])),
+        ];
+        let mut tracker = BitRateTracker::new();
+        let changes: Vec<bool> = session
+            .iter()
+            .map(|frame| tracker.add_frame(frame))
+            .collect();
+        assert_eq!(changes, vec![false, false, false, true, true, false]);
+        assert_eq!(tracker.pcd_to_picc(), BitRate::Kbps106);
+
+        tracker.add_frame(&session[2]);
+        tracker.add_frame(&session[3]);
+        assert_eq!(tracker.pcd_to_picc(), BitRate::Kbps424);
+        assert_eq!(tracker.picc_to_pcd(), BitRate::Kbps424);
+        assert_eq!(
+            tracker.pcd_to_picc().config(&DecoderConfig::new(22)),
+            DecoderConfig {
+                tick_len_divisor: 4,
+                ..DecoderConfig::new(22)
+            }
+        );
+
+        tracker.reset();
+        assert_eq!(tracker.pcd_to_picc(), BitRate::Kbps106);
+        assert_eq!(tracker.picc_to_pcd(), BitRate::Kbps106);
+    }
+
+    #[test]
+    fn bit_rate_decode() {
+        let config_106 = DecoderConfig::new(22);
+        let frames = [
+            Frame::Standard(vec![0x02, 0x00, 0xA4, 0x04, 0x00]),
+            Frame::Standard(vec![0xC2]),
+        ];
+        for bit_rate in [BitRate::Kbps212, BitRate::Kbps424, BitRate::Kbps848] {
+            let config = bit_rate.config(&config_106);
+            let mut times_set = vec![1894];
+            for frame in frames.iter() {
+                let miller_element_set = MillerElementSet::from_frame(frame);
+                times_set.extend(miller_element_set.to_times_down_with_config(&config).unwrap());
+                times_set.push(1894);
+            }
+            assert_eq!(
+                Frame::process_buffer_miller_skip_tails_with_config(&times_set, &config, |_| true),
+                frames,
+                "{bit_rate:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn chaining() {
+        let session = [
+            FrameAttributed::Miller(Frame::Standard(vec![0xE0, 0x80])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x05, 0x78, 0x80, 0x70, 0x02])),
+            FrameAttributed::Miller(Frame::Standard(vec![0x12, 0x00, 0xA4])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0xA2])),
+            FrameAttributed::Miller(Frame::Standard(vec![0x03, 0x04, 0x00])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0xF2, 0x01])),
+            FrameAttributed::Miller(Frame::Standard(vec![0xF2, 0x01])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x13, 0x6F])),
+            FrameAttributed::Miller(Frame::Standard(vec![0xA2])),
+            // retransmission after lost ACK
+            FrameAttributed::Manchester(Frame::Standard(vec![0x13, 0x6F])),
+            FrameAttributed::Miller(Frame::Standard(vec![0xA2])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x02, 0x00, 0x90, 0x00])),
+        ];
+        let mut inf_assembler = InfAssembler::new();
+        let payloads: Vec<IsoDepPayload> = session
+            .iter()
+            .filter_map(|frame| inf_assembler.add_frame(frame))
+            .collect();
+        assert_eq!(
+            payloads,
+            vec![
+                IsoDepPayload::Command(vec![0x00, 0xA4, 0x04, 0x00]),
+                IsoDepPayload::Response(vec![0x6F, 0x00, 0x90, 0x00]),
+            ]
+        );
+    }
+
+    #[test]
+    fn ats_looking_like_block() {
+        // TL 0x0A has I-block PCB bits
+        let session = [
+            FrameAttributed::Miller(Frame::Standard(vec![0xE0, 0x80])),
+            FrameAttributed::Manchester(Frame::Standard(vec![
+                0x0A, 0x78, 0x77, 0x91, 0x02, 0x80, 0x31, 0x80, 0x66, 0xB0,
+            ])),
+            FrameAttributed::Miller(Frame::Standard(vec![0x02, 0x00, 0xA4, 0x04, 0x00])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x02, 0x90, 0x00])),
+        ];
+        let mut inf_assembler = InfAssembler::new();
+        let payloads: Vec<IsoDepPayload> = session
+            .iter()
+            .filter_map(|frame| inf_assembler.add_frame(frame))
+            .collect();
+        assert_eq!(
+            payloads,
+            vec![
+                IsoDepPayload::Command(vec![0x00, 0xA4, 0x04, 0x00]),
+                IsoDepPayload::Response(vec![0x90, 0x00]),
+            ]
+        );
+    }
+}
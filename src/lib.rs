@@ -9,9 +9,22 @@ extern crate core;
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+pub mod apdu;
+pub mod config;
+pub mod emv;
 pub mod error;
+pub mod exchange;
+pub mod fdt;
 pub mod frame;
-//pub mod manchester;
+pub mod iso14443_3;
+pub mod iso14443_4;
+pub mod manchester;
+pub mod mifare_classic;
 pub mod miller;
 pub mod miller_reworked;
-//pub mod time_record_both_ways;
+pub mod picc_state;
+pub mod tick_estimate;
+pub mod tlv;
+pub mod time_record_both_ways;
+
+pub use error::Error;
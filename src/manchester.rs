@@ -3,24 +3,28 @@ use std::vec::Vec;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use bitvec::prelude::{BitVec, Lsb0};
+use bitvec::prelude::{BitSlice, BitVec, Lsb0};
 
-use crate::error::ManchesterError;
+use crate::config::DecoderConfig;
+use crate::error::{ErrorPosition, FrameError, ManchesterDecodeError, ManchesterError};
 use crate::frame::{CompleteCollector, Frame};
 use crate::time_record_both_ways::{EntryTimesBoth, SetTimesBoth};
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ManchesterElement {
     D,
     E,
     F,
+    /// Modulation in both halves of bit period, when PICCs answering at once
+    /// send different bits. Produced only in collision-aware decoding.
+    C,
 }
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum ManchesterCollector {
     Empty,
     InProgress(BitVec<u8, Lsb0>),
-    Complete(CompleteCollector),
+    Complete(Frame),
 }
 
 impl ManchesterCollector {
@@ -41,10 +45,11 @@ impl ManchesterCollector {
                     set.push(false);
                 }
                 ManchesterElement::F => {
-                    *self = ManchesterCollector::Complete(CompleteCollector {
-                        data: set.to_bitvec(),
-                    })
+                    let collector = CompleteCollector { data: &set[..] };
+                    let frame = collector.to_frame().map_err(ManchesterError::Frame)?;
+                    *self = ManchesterCollector::Complete(frame)
                 }
+                ManchesterElement::C => return Err(ManchesterError::BitCollision),
             },
             ManchesterCollector::Complete(_) => return Err(ManchesterError::NoAddingToComplete),
         }
@@ -64,14 +69,15 @@ impl ManchesterElementSet {
         }
     }
 
-    fn process_previous_d<const TICK_LEN: u16>(
+    fn process_previous_d(
         &mut self,
         time_both: EntryTimesBoth,
+        config: &DecoderConfig,
     ) -> Result<(), ManchesterError> {
-        if (time_both.first_len >= 3 * TICK_LEN) & (time_both.first_len <= 5 * TICK_LEN) {
-        } else if (time_both.first_len >= 7 * TICK_LEN) & (time_both.first_len <= 9 * TICK_LEN) {
+        if config.matches(time_both.first_len, 4) {
+        } else if config.matches(time_both.first_len, 8) {
             self.element_set.push(ManchesterElement::E)
-        } else if (time_both.first_len >= 11 * TICK_LEN) & (time_both.first_len <= 13 * TICK_LEN) {
+        } else if config.matches(time_both.first_len, 12) {
             self.element_set.push(ManchesterElement::F)
         } else {
             return Err(ManchesterError::UnexpectedOddInterval(time_both.first_len));
@@ -83,39 +89,41 @@ impl ManchesterElementSet {
                 .expect("there definitely is an element in the sequence already")
             {
                 ManchesterElement::D => {
-                    if (second_len >= 3 * TICK_LEN) & (second_len <= 5 * TICK_LEN) {
+                    if config.matches(second_len, 4) {
                         self.element_set.push(ManchesterElement::D)
                     } else {
                         return Err(ManchesterError::UnexpectedEvenInterval(second_len));
                     }
                 }
                 ManchesterElement::E => {
-                    if (second_len >= 3 * TICK_LEN) & (second_len <= 5 * TICK_LEN) {
-                    } else if (second_len >= 7 * TICK_LEN) & (second_len <= 9 * TICK_LEN) {
+                    if config.matches(second_len, 4) {
+                    } else if config.matches(second_len, 8) {
                         self.element_set.push(ManchesterElement::D)
                     } else {
                         return Err(ManchesterError::UnexpectedEvenInterval(second_len));
                     }
                 }
                 ManchesterElement::F => {
-                    if (second_len >= 3 * TICK_LEN) & (second_len <= 5 * TICK_LEN) {
+                    if config.matches(second_len, 4) {
                         self.element_set.push(ManchesterElement::D)
                     } else {
                         return Err(ManchesterError::UnexpectedEvenInterval(second_len));
                     }
                 }
+                ManchesterElement::C => unreachable!(),
             }
         }
         Ok(())
     }
 
-    fn process_previous_e<const TICK_LEN: u16>(
+    fn process_previous_e(
         &mut self,
         time_both: EntryTimesBoth,
+        config: &DecoderConfig,
     ) -> Result<(), ManchesterError> {
-        if (time_both.first_len >= 3 * TICK_LEN) & (time_both.first_len <= 5 * TICK_LEN) {
+        if config.matches(time_both.first_len, 4) {
             self.element_set.push(ManchesterElement::E)
-        } else if (time_both.first_len >= 7 * TICK_LEN) & (time_both.first_len <= 9 * TICK_LEN) {
+        } else if config.matches(time_both.first_len, 8) {
             self.element_set.push(ManchesterElement::F)
         } else {
             return Err(ManchesterError::UnexpectedOddInterval(time_both.first_len));
@@ -126,17 +134,17 @@ impl ManchesterElementSet {
                 .last()
                 .expect("there definitely is an element in the sequence already")
             {
-                ManchesterElement::D => unreachable!(),
+                ManchesterElement::D | ManchesterElement::C => unreachable!(),
                 ManchesterElement::E => {
-                    if (second_len >= 3 * TICK_LEN) & (second_len <= 5 * TICK_LEN) {
-                    } else if (second_len >= 7 * TICK_LEN) & (second_len <= 9 * TICK_LEN) {
+                    if config.matches(second_len, 4) {
+                    } else if config.matches(second_len, 8) {
                         self.element_set.push(ManchesterElement::D)
                     } else {
                         return Err(ManchesterError::UnexpectedEvenInterval(second_len));
                     }
                 }
                 ManchesterElement::F => {
-                    if (second_len >= 3 * TICK_LEN) & (second_len <= 5 * TICK_LEN) {
+                    if config.matches(second_len, 4) {
                         self.element_set.push(ManchesterElement::D)
                     } else {
                         return Err(ManchesterError::UnexpectedEvenInterval(second_len));
@@ -147,43 +155,235 @@ impl ManchesterElementSet {
         Ok(())
     }
 
+    pub(crate) fn decode_error(
+        &self,
+        error: ManchesterError,
+        offset: usize,
+        chunk: usize,
+    ) -> ManchesterDecodeError {
+        ManchesterDecodeError {
+            error,
+            position: ErrorPosition::new(offset, chunk, &self.element_set),
+        }
+    }
+
     /// Modulation is always present on the PICC. modulation is suppressed for
     /// the duration of F element to indicate the end of frame.
     /// Outer long time intervals are modulated.
-    pub(crate) fn add_time_both_interval<const TICK_LEN: u16>(
+    pub(crate) fn add_time_both_interval(
         &mut self,
         time_both: EntryTimesBoth,
+        config: &DecoderConfig,
     ) -> Result<(), ManchesterError> {
         match self.element_set.last() {
             None => {
                 self.element_set.push(ManchesterElement::D);
-                self.process_previous_d::<TICK_LEN>(time_both)
+                self.process_previous_d(time_both, config)
+            }
+            Some(ManchesterElement::D) => self.process_previous_d(time_both, config),
+            Some(ManchesterElement::E) => self.process_previous_e(time_both, config),
+            Some(ManchesterElement::F) | Some(ManchesterElement::C) => unreachable!(),
+        }
+    }
+
+    /// Elements from half-bit levels, `true` for modulated half, each with
+    /// index of its interval in the buffer.
+    ///
+    /// Halves go in pairs, the first pair is start of frame. Modulation in
+    /// both halves is a collision. Halves after the end of frame must be
+    /// unmodulated.
+    pub(crate) fn from_half_bits(
+        half_bits: &[(bool, usize)],
+        chunk: usize,
+    ) -> Result<Self, ManchesterDecodeError> {
+        let mut manchester_element_set = Self::new();
+        for pair in half_bits.chunks(2) {
+            if let Some(ManchesterElement::F) = manchester_element_set.element_set.last() {
+                if let Some((_, offset)) = pair.iter().find(|(modulated, _)| *modulated) {
+                    return Err(manchester_element_set.decode_error(
+                        ManchesterError::NoAddingToComplete,
+                        *offset,
+                        chunk,
+                    ));
+                }
+                continue;
             }
-            Some(ManchesterElement::D) => self.process_previous_d::<TICK_LEN>(time_both),
-            Some(ManchesterElement::E) => self.process_previous_e::<TICK_LEN>(time_both),
-            Some(ManchesterElement::F) => unreachable!(),
+            let element = match pair {
+                [(true, _), (false, _)] => ManchesterElement::D,
+                [(false, _), (true, _)] => ManchesterElement::E,
+                [(false, _), (false, _)] => ManchesterElement::F,
+                [(true, _), (true, _)] => ManchesterElement::C,
+                [(_, offset)] => {
+                    return Err(manchester_element_set.decode_error(
+                        ManchesterError::IncompleteFrame,
+                        *offset,
+                        chunk,
+                    ))
+                }
+                _ => unreachable!(),
+            };
+            manchester_element_set.element_set.push(element);
         }
+        Ok(manchester_element_set)
     }
 
     pub fn from_times_both<const TICK_LEN: u16>(
         times_both: SetTimesBoth<TICK_LEN>,
-    ) -> Result<Self, ManchesterError> {
+    ) -> Result<Self, ManchesterDecodeError> {
         times_both.convert_to_manchester()
     }
 
+    /// Elements for transmitting the bits, with start and end of frame.
+    ///
+    /// Start of frame is D. Logic 1 is D, logic 0 is E. End of frame is F.
+    pub fn from_bits(bits: &BitSlice<u8, Lsb0>) -> Self {
+        let mut manchester_element_set = Self::new();
+        manchester_element_set.element_set.push(ManchesterElement::D);
+        for bit in bits.iter().by_vals() {
+            if bit {
+                manchester_element_set.element_set.push(ManchesterElement::D)
+            } else {
+                manchester_element_set.element_set.push(ManchesterElement::E)
+            }
+        }
+        manchester_element_set.element_set.push(ManchesterElement::F);
+        manchester_element_set
+    }
+
+    pub fn from_frame(frame: &Frame) -> Self {
+        Self::from_bits(&frame.to_bits())
+    }
+
+    /// Alternating unmodulated and modulated intervals, as parsed by
+    /// [`SetTimesBoth`].
+    ///
+    /// D is modulated in the first half of bit period, E in the second half,
+    /// F is not modulated. The first interval is unmodulated and starts after
+    /// the modulated first half of start of frame, i.e. the load is switched
+    /// on for 4 ticks before it. The last interval is unmodulated and
+    /// includes F.
+    ///
+    /// Fails if an interval in timer counts does not fit in `u16`.
+    pub fn to_times_both<const TICK_LEN: u16>(&self) -> Result<Vec<u16>, ManchesterError> {
+        self.to_times_both_with_config(&DecoderConfig::new(TICK_LEN))
+    }
+
+    pub fn to_times_both_with_config(
+        &self,
+        config: &DecoderConfig,
+    ) -> Result<Vec<u16>, ManchesterError> {
+        let interval = |halves: u64| {
+            u16::try_from(config.ticks_to_counts(halves * 4))
+                .map_err(|_| ManchesterError::IntervalOverflow)
+        };
+        let mut times_both = Vec::new();
+        let mut current_modulated = false;
+        let mut current_halves: u64 = 0;
+        let halves = self
+            .element_set
+            .iter()
+            .flat_map(|element| match element {
+                ManchesterElement::D => [true, false],
+                ManchesterElement::E => [false, true],
+                ManchesterElement::F => [false, false],
+                ManchesterElement::C => [true, true],
+            })
+            .skip(1);
+        for modulated in halves {
+            if modulated != current_modulated {
+                times_both.push(interval(current_halves)?);
+                current_modulated = modulated;
+                current_halves = 0;
+            }
+            current_halves += 1;
+        }
+        if current_halves != 0 {
+            times_both.push(interval(current_halves)?);
+        }
+        Ok(times_both)
+    }
+
     pub fn collect_frame(&self) -> Result<Frame, ManchesterError> {
         let mut collector = ManchesterCollector::Empty;
         for element in self.element_set.iter() {
             collector.add_element(element)?;
         }
-        if let ManchesterCollector::Complete(complete_collector) = collector {
-            complete_collector
-                .to_frame()
-                .map_err(ManchesterError::Frame)
+        if let ManchesterCollector::Complete(frame) = collector {
+            Ok(frame)
         } else {
             Err(ManchesterError::IncompleteFrame)
         }
     }
+
+    /// Frame bits with collided positions, from collision-aware decoding.
+    pub fn collect_collision_frame(&self) -> Result<ManchesterCollisionFrame, ManchesterError> {
+        let mut elements = self.element_set.iter();
+        if elements.next() != Some(&ManchesterElement::D) {
+            return Err(ManchesterError::FirstNotD);
+        }
+        let mut bits: BitVec<u8, Lsb0> = BitVec::new();
+        let mut collisions: BitVec<u8, Lsb0> = BitVec::new();
+        for element in elements {
+            match element {
+                ManchesterElement::D => {
+                    bits.push(true);
+                    collisions.push(false);
+                }
+                ManchesterElement::E => {
+                    bits.push(false);
+                    collisions.push(false);
+                }
+                ManchesterElement::C => {
+                    bits.push(true);
+                    collisions.push(true);
+                }
+                ManchesterElement::F => {
+                    if bits.is_empty() {
+                        return Err(ManchesterError::Frame(FrameError::EmptyFrame));
+                    }
+                    return Ok(ManchesterCollisionFrame { bits, collisions });
+                }
+            }
+        }
+        Err(ManchesterError::IncompleteFrame)
+    }
+}
+
+/// Manchester frame decoded in collision-aware mode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManchesterCollisionFrame {
+    /// Received bits, including parity bits; collided bits are read as 1.
+    pub bits: BitVec<u8, Lsb0>,
+    /// Collided bit positions in `bits`.
+    pub collisions: BitVec<u8, Lsb0>,
+}
+
+impl ManchesterCollisionFrame {
+    /// Position of the first collided bit in `bits`, parity bits included.
+    pub fn first_collision(&self) -> Option<usize> {
+        self.collisions.first_one()
+    }
+
+    /// Position of the first collided data bit, with every 9th (parity) bit
+    /// skipped.
+    ///
+    /// This is the number of data bits received without collision, as used by
+    /// PCD to build the next ANTICOLLISION command.
+    pub fn first_collision_data_bit(&self) -> Option<usize> {
+        self.collisions
+            .iter_ones()
+            .find(|position| position % 9 != 8)
+            .map(|position| position - position / 9)
+    }
+
+    pub fn has_collisions(&self) -> bool {
+        self.collisions.any()
+    }
+
+    /// Frame from received bits, with collided bits read as 1.
+    pub fn to_frame(&self) -> Result<Frame, FrameError> {
+        CompleteCollector { data: &self.bits }.to_frame()
+    }
 }
 
 impl Default for ManchesterElementSet {
@@ -196,6 +396,8 @@ impl Default for ManchesterElementSet {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::FrameError;
+    use crate::time_record_both_ways::TimesBoth;
     use bitvec::prelude::bitvec;
 
     #[test]
@@ -233,12 +435,9 @@ mod tests {
     #[test]
     fn manchester_collector_04() {
         let mut collector = ManchesterCollector::InProgress(BitVec::<u8, Lsb0>::new());
-        collector.add_element(&ManchesterElement::F).unwrap();
         assert_eq!(
-            collector,
-            ManchesterCollector::Complete(CompleteCollector {
-                data: BitVec::<u8, Lsb0>::new()
-            })
+            collector.add_element(&ManchesterElement::F),
+            Err(ManchesterError::Frame(FrameError::EmptyFrame))
         );
     }
 
@@ -268,9 +467,7 @@ mod tests {
         collector.add_element(&ManchesterElement::F).unwrap();
         assert_eq!(
             collector,
-            ManchesterCollector::Complete(CompleteCollector {
-                data: bitvec![u8, Lsb0; 0]
-            })
+            ManchesterCollector::Complete(Frame::Short(0x00))
         );
     }
 
@@ -300,9 +497,7 @@ mod tests {
         collector.add_element(&ManchesterElement::F).unwrap();
         assert_eq!(
             collector,
-            ManchesterCollector::Complete(CompleteCollector {
-                data: bitvec![u8, Lsb0; 1]
-            })
+            ManchesterCollector::Complete(Frame::Short(0x01))
         );
     }
 
@@ -322,9 +517,7 @@ mod tests {
         }
         assert_eq!(
             collector,
-            ManchesterCollector::Complete(CompleteCollector {
-                data: bitvec![u8, Lsb0; 1, 0, 0, 1]
-            })
+            ManchesterCollector::Complete(Frame::Short(0x09))
         );
     }
 
@@ -342,9 +535,7 @@ mod tests {
         }
         assert_eq!(
             collector,
-            ManchesterCollector::Complete(CompleteCollector {
-                data: bitvec![u8, Lsb0; 1, 0]
-            })
+            ManchesterCollector::Complete(Frame::Short(0x01))
         );
     }
 
@@ -360,4 +551,148 @@ mod tests {
         let frame = manchester_element_set.collect_frame().unwrap();
         assert_eq!(frame, Frame::Standard(vec![0xA3]));
     }
+
+    #[test]
+    fn manchester_time_both_config() {
+        // same as `manchester_time_both_1`, recorded with timer 1.5 times faster
+        let times_set = [
+            2610, 145, 123, 145, 120, 264, 141, 144, 123, 147, 250, 270, 268, 153, 121, 145, 121,
+            147, 121, 145, 121, 147, 121, 264, 267, 153, 123, 262, 268, 271, 267, 153, 120, 265,
+            139, 147, 120, 147, 250, 151, 123, 145, 123, 384, 43054,
+        ];
+        let chunk = &TimesBoth::from_raw(&times_set, DecoderConfig::new(33))[0];
+        let manchester_element_set = chunk.convert_to_manchester().unwrap();
+        let frame = manchester_element_set.collect_frame().unwrap();
+        assert_eq!(frame, Frame::Standard(vec![0xA3]));
+    }
+
+    #[test]
+    fn manchester_time_both_position() {
+        // same as `manchester_time_both_1`, with 20th interval broken
+        let times_set = [
+            1740, 97, 82, 97, 80, 176, 94, 96, 82, 98, 167, 180, 179, 102, 81, 97, 81, 98, 81, 140,
+            81, 98, 81, 176, 178, 102, 82, 175, 179, 181, 178, 102, 80, 177, 93, 98, 80, 98, 167,
+            101, 82, 97, 82, 256, 28703,
+        ];
+        let chunk = &TimesBoth::from_raw(&times_set, DecoderConfig::new(22))[0];
+        let error = chunk.convert_to_manchester().unwrap_err();
+        assert_eq!(error.error, ManchesterError::UnexpectedOddInterval(140));
+        assert_eq!(error.position.offset, 19);
+        assert_eq!(error.position.chunk, 1);
+    }
+
+    #[test]
+    fn manchester_generate_1() {
+        let manchester_element_set = ManchesterElementSet::from_frame(&Frame::Short(0x05));
+        assert_eq!(
+            manchester_element_set.element_set,
+            vec![
+                ManchesterElement::D,
+                ManchesterElement::D,
+                ManchesterElement::E,
+                ManchesterElement::D,
+                ManchesterElement::E,
+                ManchesterElement::E,
+                ManchesterElement::E,
+                ManchesterElement::E,
+                ManchesterElement::F,
+            ]
+        );
+        assert_eq!(
+            manchester_element_set.to_times_both::<22u16>().unwrap(),
+            vec![88, 88, 176, 176, 176, 88, 88, 88, 88, 88, 88, 88, 176]
+        );
+        assert_eq!(manchester_element_set.collect_frame(), Ok(Frame::Short(0x05)));
+        assert_eq!(
+            manchester_element_set.to_times_both::<10000u16>(),
+            Err(ManchesterError::IntervalOverflow)
+        );
+    }
+
+    #[test]
+    fn manchester_generate_2() {
+        let frames = [
+            Frame::Standard(vec![0xA3]),
+            Frame::SddCleanCut(vec![0x04, 0x00]),
+            Frame::SddCleanCut(vec![0x88, 0x04, 0x21, 0x32, 0x8F]),
+            Frame::Standard(vec![0x08, 0xB6, 0xDD]),
+            Frame::Short(0x0A),
+        ];
+        for frame in frames.iter() {
+            let manchester_element_set = ManchesterElementSet::from_frame(frame);
+            assert_eq!(manchester_element_set.collect_frame().as_ref(), Ok(frame));
+            let mut times_set = vec![1740];
+            times_set.extend(manchester_element_set.to_times_both::<22u16>().unwrap());
+            times_set.push(28703);
+            let chunk = SetTimesBoth::<22u16>::from_raw(&times_set).remove(0);
+            let decoded = ManchesterElementSet::from_times_both(chunk).unwrap();
+            assert_eq!(decoded.collect_frame().as_ref(), Ok(frame));
+        }
+    }
+
+    #[test]
+    fn manchester_collisions_none() {
+        let times_set = [
+            1740, 97, 82, 97, 80, 176, 94, 96, 82, 98, 167, 180, 179, 102, 81, 97, 81, 98, 81, 97,
+            81, 98, 81, 176, 178, 102, 82, 175, 179, 181, 178, 102, 80, 177, 93, 98, 80, 98, 167,
+            101, 82, 97, 82, 256, 28703,
+        ];
+        let chunk = &TimesBoth::from_raw_collisions(&times_set, DecoderConfig::new(22))[0];
+        let manchester_element_set = chunk.convert_to_manchester_collisions().unwrap();
+        assert_eq!(
+            manchester_element_set,
+            chunk.convert_to_manchester().unwrap()
+        );
+        let collision_frame = manchester_element_set.collect_collision_frame().unwrap();
+        assert!(!collision_frame.has_collisions());
+        assert_eq!(collision_frame.to_frame(), Ok(Frame::Standard(vec![0xA3])));
+    }
+
+    #[test]
+    fn manchester_collisions() {
+        // two PICCs answer ANTICOLLISION, UIDs differ in 4 consecutive bits
+        // of the third byte and in parity bit of that byte
+        let bits_a = Frame::SddCleanCut(vec![0x88, 0x04, 0x21, 0x32, 0x9F]).to_bits();
+        let bits_b = Frame::SddCleanCut(vec![0x88, 0x04, 0x2E, 0x32, 0x9F]).to_bits();
+        let collisions = bits_a.clone() ^ bits_b.clone();
+        let mut manchester_element_set = ManchesterElementSet::from_bits(&bits_a);
+        for position in collisions.iter_ones() {
+            manchester_element_set.element_set[position + 1] = ManchesterElement::C;
+        }
+        let mut times_set = vec![1740];
+        times_set.extend(manchester_element_set.to_times_both::<22u16>().unwrap());
+        times_set.push(28703);
+
+        // collided bits make modulated run longer than the usual idle gap
+        for chunk in TimesBoth::from_raw(&times_set, DecoderConfig::new(22)).iter() {
+            assert!(!chunk
+                .convert_to_manchester()
+                .is_ok_and(|set| set.collect_frame().is_ok()));
+        }
+
+        let chunk = &TimesBoth::from_raw_collisions(&times_set, DecoderConfig::new(22))[0];
+        let decoded = chunk.convert_to_manchester_collisions().unwrap();
+        assert_eq!(decoded, manchester_element_set);
+        assert_eq!(decoded.collect_frame(), Err(ManchesterError::BitCollision));
+        let collision_frame = decoded.collect_collision_frame().unwrap();
+        assert_eq!(collision_frame.collisions, collisions);
+        assert_eq!(collision_frame.first_collision(), Some(18));
+        assert_eq!(collision_frame.first_collision_data_bit(), Some(16));
+        assert_eq!(collision_frame.bits, bits_a | bits_b);
+    }
+
+    #[test]
+    fn manchester_collisions_position() {
+        // same as `manchester_time_both_position`
+        let times_set = [
+            1740, 97, 82, 97, 80, 176, 94, 96, 82, 98, 167, 180, 179, 102, 81, 97, 81, 98, 81, 140,
+            81, 98, 81, 176, 178, 102, 82, 175, 179, 181, 178, 102, 80, 177, 93, 98, 80, 98, 167,
+            101, 82, 97, 82, 256, 28703,
+        ];
+        let chunk = &TimesBoth::from_raw_collisions(&times_set, DecoderConfig::new(22))[0];
+        let error = chunk.convert_to_manchester_collisions().unwrap_err();
+        assert_eq!(error.error, ManchesterError::UnexpectedOddInterval(140));
+        assert_eq!(error.position.offset, 19);
+        assert_eq!(error.position.chunk, 1);
+    }
 }
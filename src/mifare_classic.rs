@@ -0,0 +1,367 @@
+use core::fmt;
+
+use crate::frame::Frame;
+use crate::iso14443_3::HLTA;
+
+pub const AUTH_KEY_A: u8 = 0x60;
+pub const AUTH_KEY_B: u8 = 0x61;
+pub const READ: u8 = 0x30;
+pub const WRITE: u8 = 0xA0;
+pub const DECREMENT: u8 = 0xC0;
+pub const INCREMENT: u8 = 0xC1;
+pub const RESTORE: u8 = 0xC2;
+pub const TRANSFER: u8 = 0xB0;
+
+/// 4-bit acknowledge sent by PICC.
+pub const ACK: u8 = 0x0A;
+
+/// Sector holding the block, for MIFARE Classic 1K and 4K.
+///
+/// Blocks 0..128 are in sectors of 4 blocks, blocks 128..256 (4K only) are in
+/// sectors of 16 blocks, starting from sector 32.
+pub fn sector(block: u8) -> u8 {
+    if block < 128 {
+        block / 4
+    } else {
+        32 + (block - 128) / 16
+    }
+}
+
+/// Last block of a sector, holding keys and access bits.
+pub fn is_sector_trailer(block: u8) -> bool {
+    if block < 128 {
+        block % 4 == 3
+    } else {
+        block % 16 == 15
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyType {
+    A,
+    B,
+}
+
+/// MIFARE Classic command sent by PCD.
+///
+/// Only the plaintext phase is readable: after AUTH the frames are encrypted
+/// with Crypto1 and show up as [`MifareCommand::Unknown`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MifareCommand {
+    Auth {
+        key: KeyType,
+        block: u8,
+    },
+    Read {
+        block: u8,
+    },
+    Write {
+        block: u8,
+    },
+    /// Second part of WRITE, sent after PICC acknowledged the first one.
+    WriteData {
+        block: u8,
+        data: [u8; 16],
+    },
+    Decrement {
+        block: u8,
+    },
+    Increment {
+        block: u8,
+    },
+    Restore {
+        block: u8,
+    },
+    /// Second part of DECREMENT, INCREMENT or RESTORE, sent after PICC
+    /// acknowledged the first one.
+    Operand {
+        block: u8,
+        value: i32,
+    },
+    Transfer {
+        block: u8,
+    },
+    Halt,
+    Unknown(Frame),
+}
+
+impl MifareCommand {
+    pub fn from_frame(frame: &Frame) -> Self {
+        match frame {
+            Frame::Standard(data) => match data.as_slice() {
+                [AUTH_KEY_A, block] => MifareCommand::Auth {
+                    key: KeyType::A,
+                    block: *block,
+                },
+                [AUTH_KEY_B, block] => MifareCommand::Auth {
+                    key: KeyType::B,
+                    block: *block,
+                },
+                [READ, block] => MifareCommand::Read { block: *block },
+                [WRITE, block] => MifareCommand::Write { block: *block },
+                [DECREMENT, block] => MifareCommand::Decrement { block: *block },
+                [INCREMENT, block] => MifareCommand::Increment { block: *block },
+                [RESTORE, block] => MifareCommand::Restore { block: *block },
+                [TRANSFER, block] => MifareCommand::Transfer { block: *block },
+                [HLTA, 0x00] => MifareCommand::Halt,
+                _ => MifareCommand::Unknown(frame.clone()),
+            },
+            _ => MifareCommand::Unknown(frame.clone()),
+        }
+    }
+
+    /// Frame sent by PCD, with the preceding PCD command known.
+    ///
+    /// Data for WRITE and operand for value operations are recognized by
+    /// length. Frames that do not fit are classified with
+    /// [`MifareCommand::from_frame`].
+    pub fn from_frame_after(frame: &Frame, previous: &MifareCommand) -> Self {
+        if let Frame::Standard(data) = frame {
+            match previous {
+                MifareCommand::Write { block } => {
+                    if let Ok(data) = data.as_slice().try_into() {
+                        return MifareCommand::WriteData {
+                            block: *block,
+                            data,
+                        };
+                    }
+                }
+                MifareCommand::Decrement { block }
+                | MifareCommand::Increment { block }
+                | MifareCommand::Restore { block } => {
+                    if let Ok(value) = data.as_slice().try_into() {
+                        return MifareCommand::Operand {
+                            block: *block,
+                            value: i32::from_le_bytes(value),
+                        };
+                    }
+                }
+                _ => {}
+            }
+        }
+        MifareCommand::from_frame(frame)
+    }
+
+    /// Block the command operates on.
+    pub fn block(&self) -> Option<u8> {
+        match self {
+            MifareCommand::Auth { block, .. }
+            | MifareCommand::Read { block }
+            | MifareCommand::Write { block }
+            | MifareCommand::WriteData { block, .. }
+            | MifareCommand::Decrement { block }
+            | MifareCommand::Increment { block }
+            | MifareCommand::Restore { block }
+            | MifareCommand::Operand { block, .. }
+            | MifareCommand::Transfer { block } => Some(*block),
+            MifareCommand::Halt | MifareCommand::Unknown(_) => None,
+        }
+    }
+
+    /// Sector of the block the command operates on.
+    pub fn sector(&self) -> Option<u8> {
+        self.block().map(sector)
+    }
+}
+
+impl fmt::Display for MifareCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (name, block) = match self {
+            MifareCommand::Auth {
+                key: KeyType::A,
+                block,
+            } => ("AUTH A", block),
+            MifareCommand::Auth {
+                key: KeyType::B,
+                block,
+            } => ("AUTH B", block),
+            MifareCommand::Read { block } => ("READ", block),
+            MifareCommand::Write { block } => ("WRITE", block),
+            MifareCommand::WriteData { block, .. } => ("WRITE data", block),
+            MifareCommand::Decrement { block } => ("DECREMENT", block),
+            MifareCommand::Increment { block } => ("INCREMENT", block),
+            MifareCommand::Restore { block } => ("RESTORE", block),
+            MifareCommand::Operand { block, .. } => ("operand", block),
+            MifareCommand::Transfer { block } => ("TRANSFER", block),
+            MifareCommand::Halt => return write!(f, "HALT"),
+            MifareCommand::Unknown(frame) => return write!(f, "unknown {frame:X?}"),
+        };
+        write!(f, "{name} block {block} (sector {})", sector(*block))?;
+        match self {
+            MifareCommand::WriteData { data, .. } => write!(f, ": {data:02X?}"),
+            MifareCommand::Operand { value, .. } => write!(f, ": {value}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// MIFARE Classic answer sent by PICC.
+///
+/// Answers are recognized by the frame layout alone: ACK and NAK are 4 bits,
+/// AUTH nonce is 4 bytes with no CRC, READ data is 16 bytes with CRC. Short
+/// frame with any of the upper 3 bits set is not ACK or NAK.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MifareAnswer {
+    Ack,
+    /// Negative acknowledge, with its 4-bit code.
+    Nak(u8),
+    /// Tag nonce nT, first answer to AUTH.
+    Nonce([u8; 4]),
+    Data([u8; 16]),
+    Unknown(Frame),
+}
+
+impl MifareAnswer {
+    pub fn from_frame(frame: &Frame) -> Self {
+        match frame {
+            Frame::Short(ACK) => MifareAnswer::Ack,
+            Frame::Short(code) if code & 0xF0 == 0 => MifareAnswer::Nak(*code),
+            Frame::SddCleanCut(data) => match data.as_slice().try_into() {
+                Ok(nonce) => MifareAnswer::Nonce(nonce),
+                Err(_) => MifareAnswer::Unknown(frame.clone()),
+            },
+            Frame::Standard(data) => match data.as_slice().try_into() {
+                Ok(data) => MifareAnswer::Data(data),
+                Err(_) => MifareAnswer::Unknown(frame.clone()),
+            },
+            _ => MifareAnswer::Unknown(frame.clone()),
+        }
+    }
+}
+
+impl fmt::Display for MifareAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MifareAnswer::Ack => write!(f, "ACK"),
+            MifareAnswer::Nak(code) => {
+                let reason = match code {
+                    0x0 | 0x4 => "invalid operation",
+                    0x1 | 0x5 => "parity or CRC error",
+                    _ => "unknown",
+                };
+                write!(f, "NAK {code:X} ({reason})")
+            }
+            MifareAnswer::Nonce(nonce) => write!(f, "nonce {nonce:02X?}"),
+            MifareAnswer::Data(data) => write!(f, "data {data:02X?}"),
+            MifareAnswer::Unknown(frame) => write!(f, "unknown {frame:X?}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::CompleteCollector;
+    use bitvec::prelude::{bitvec, Lsb0};
+    use std::string::ToString;
+
+    #[test]
+    fn sectors() {
+        assert_eq!(sector(0), 0);
+        assert_eq!(sector(7), 1);
+        assert_eq!(sector(127), 31);
+        assert_eq!(sector(128), 32);
+        assert_eq!(sector(255), 39);
+        assert!(is_sector_trailer(3));
+        assert!(!is_sector_trailer(4));
+        assert!(is_sector_trailer(143));
+        assert!(!is_sector_trailer(131));
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!(
+            MifareCommand::from_frame(&Frame::Standard(vec![0x60, 0x07])),
+            MifareCommand::Auth {
+                key: KeyType::A,
+                block: 7
+            }
+        );
+        assert_eq!(
+            MifareCommand::from_frame(&Frame::Standard(vec![0x61, 0x90])).sector(),
+            Some(33)
+        );
+        assert_eq!(
+            MifareCommand::from_frame(&Frame::Standard(vec![0x50, 0x00])),
+            MifareCommand::Halt
+        );
+        assert_eq!(
+            MifareCommand::from_frame(&Frame::Standard(vec![0x30, 0x04])).to_string(),
+            "READ block 4 (sector 1)"
+        );
+        assert_eq!(
+            MifareCommand::from_frame(&Frame::Standard(vec![0x30, 0x04, 0x00])),
+            MifareCommand::Unknown(Frame::Standard(vec![0x30, 0x04, 0x00]))
+        );
+    }
+
+    #[test]
+    fn second_parts() {
+        let write = MifareCommand::from_frame(&Frame::Standard(vec![0xA0, 0x05]));
+        assert_eq!(write, MifareCommand::Write { block: 5 });
+        assert_eq!(
+            MifareCommand::from_frame_after(&Frame::Standard(vec![0xAB; 16]), &write),
+            MifareCommand::WriteData {
+                block: 5,
+                data: [0xAB; 16]
+            }
+        );
+        let decrement = MifareCommand::from_frame(&Frame::Standard(vec![0xC0, 0x05]));
+        let operand = MifareCommand::from_frame_after(
+            &Frame::Standard(vec![0x0A, 0x00, 0x00, 0x00]),
+            &decrement,
+        );
+        assert_eq!(
+            operand,
+            MifareCommand::Operand {
+                block: 5,
+                value: 10
+            }
+        );
+        assert_eq!(operand.to_string(), "operand block 5 (sector 1): 10");
+        assert_eq!(
+            MifareCommand::from_frame_after(&Frame::Standard(vec![0xB0, 0x05]), &operand),
+            MifareCommand::Transfer { block: 5 }
+        );
+    }
+
+    #[test]
+    fn answers() {
+        let ack = CompleteCollector {
+            data: &bitvec![u8, Lsb0; 0, 1, 0, 1],
+        }
+        .to_frame()
+        .unwrap();
+        assert_eq!(MifareAnswer::from_frame(&ack), MifareAnswer::Ack);
+        // frame cut from a longer record
+        let ack = CompleteCollector {
+            data: &bitvec![u8, Lsb0; 0, 1, 0, 1, 1, 1, 1, 1][..4],
+        }
+        .to_frame()
+        .unwrap();
+        assert_eq!(MifareAnswer::from_frame(&ack), MifareAnswer::Ack);
+        let nak = CompleteCollector {
+            data: &bitvec![u8, Lsb0; 0, 0, 1, 0],
+        }
+        .to_frame()
+        .unwrap();
+        assert_eq!(MifareAnswer::from_frame(&nak), MifareAnswer::Nak(0x4));
+        assert_eq!(
+            MifareAnswer::from_frame(&nak).to_string(),
+            "NAK 4 (invalid operation)"
+        );
+        assert_eq!(
+            MifareAnswer::from_frame(&Frame::Short(0x26)),
+            MifareAnswer::Unknown(Frame::Short(0x26))
+        );
+        assert_eq!(
+            MifareAnswer::from_frame(&Frame::SddCleanCut(vec![0x01, 0x02, 0x03, 0x04])),
+            MifareAnswer::Nonce([0x01, 0x02, 0x03, 0x04])
+        );
+        assert_eq!(
+            MifareAnswer::from_frame(&Frame::Standard(vec![0x00; 16])),
+            MifareAnswer::Data([0x00; 16])
+        );
+    }
+}
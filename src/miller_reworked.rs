@@ -3,39 +3,51 @@ use std::vec::Vec;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use bitvec::prelude::{BitVec, Lsb0};
+use bitvec::prelude::{BitSlice, BitVec, Lsb0};
 
-use crate::error::MillerError;
-use crate::frame::{CompleteCollector, Frame};
-//use crate::time_record_both_ways::{EntryTimesBoth, SetTimesBoth};
+use crate::config::DecoderConfig;
+use crate::error::{ErrorPosition, MillerDecodeError, MillerError};
+use crate::frame::{CompleteCollector, Frame, TimedFrame};
+use crate::time_record_both_ways::{EntryTimesBoth, SetTimesBoth};
+
+/// Shortest pause accepted in both-edge Miller decoding, in ticks.
+///
+/// ISO 14443-2 sets the PCD pause length `t1` between 28/fc and 40.5/fc,
+/// i.e. 1.75 to 2.5 ticks. Envelope detector adds about a tick to the
+/// recorded pause, so shorter recorded pauses are glitches.
+pub const MILLER_PAUSE_MIN_TICKS: u16 = 3;
+
+/// Longest pause accepted in both-edge Miller decoding, in ticks.
+///
+/// Longer modulation is not a Miller pause, and could not be followed by
+/// a valid bit boundary anyway.
+pub const MILLER_PAUSE_MAX_TICKS: u16 = 5;
 
 impl Frame {
     pub fn process_buffer_miller_skip_tails<P, const TICK_LEN: u16>(buffer: &[u16], frame_filter: P) -> Vec<Self>
         where P: Fn(&Self) -> bool
     {
-        let iter = buffer.split(|interval| *interval > 19 * TICK_LEN);
-        let iter_len = buffer.split(|interval| *interval > 19 * TICK_LEN).count();
+        Self::process_buffer_miller_skip_tails_with_config(buffer, &DecoderConfig::new(TICK_LEN), frame_filter)
+    }
+
+    pub fn process_buffer_miller_skip_tails_with_config<P>(buffer: &[u16], config: &DecoderConfig, frame_filter: P) -> Vec<Self>
+        where P: Fn(&Self) -> bool
+    {
+        let iter = buffer.split(|interval| config.is_idle_down(*interval));
+        let iter_len = buffer.split(|interval| config.is_idle_down(*interval)).count();
         if iter_len > 2 {
             let mut frames_set = Vec::new();
             for times_set in iter.skip(1).take(iter_len-2) {
                 let mut miller_element_set = MillerElementSet::new();
                 let mut flag_not_miller = false;
-                for time_interval in times_set.into_iter() {
-                    if let Err(_) = miller_element_set.add_time_down_interval::<TICK_LEN>(*time_interval) {
+                for time_interval in times_set.iter() {
+                    if miller_element_set.add_time_down_interval(*time_interval, config).is_err() {
                         flag_not_miller = true;
                         break;
                     };
                 }
                 if flag_not_miller {break;}
-                match miller_element_set.element_set.last() {
-                    None => {},
-                    Some(MillerElement::X) => {
-                        miller_element_set.element_set.push(MillerElement::Y);
-                        miller_element_set.element_set.push(MillerElement::Y);
-                    },
-                    Some(MillerElement::Y) => unreachable!(),
-                    Some(MillerElement::Z) => miller_element_set.element_set.push(MillerElement::Y),
-                }
+                miller_element_set.add_end_of_frame();
                 if let Ok(frame) = miller_element_set.collect_frame() {
                     if frame_filter(&frame) {
                         frames_set.push(frame)
@@ -46,9 +58,78 @@ impl Frame {
         }
         else {Vec::new()}
     }
+
+    /// Same as [`Frame::process_buffer_miller_skip_tails_with_config`], but
+    /// chunk that could not be decoded is skipped and the processing goes on
+    /// from the next idle gap.
+    pub fn process_buffer_miller_skip_tails_tolerant<P>(buffer: &[u16], config: &DecoderConfig, frame_filter: P) -> MillerBufferFrames
+        where P: Fn(&Self) -> bool
+    {
+        let mut buffer_frames = MillerBufferFrames::default();
+        let chunks = MillerTimesDown::from_raw(buffer, *config);
+        let iter_len = chunks.len();
+        for times_down in chunks.into_iter().skip(1).take(iter_len.saturating_sub(2)) {
+            if times_down.time_down_set.is_empty() {
+                continue;
+            }
+            match times_down.convert() {
+                Ok(frame) => {
+                    if frame_filter(&frame) {
+                        buffer_frames.frames.push(frame)
+                    }
+                }
+                Err(error) => buffer_frames.errors.push(error),
+            }
+        }
+        buffer_frames
+    }
+
+    /// Same as [`Frame::process_buffer_miller_skip_tails_tolerant`], with
+    /// time of the first and the last pause of each frame.
+    ///
+    /// `capture_start` is the time of the first edge in the buffer, in timer
+    /// counts; 0 gives times relative to the buffer start.
+    pub fn process_buffer_miller_timed<P>(buffer: &[u16], config: &DecoderConfig, capture_start: u64, frame_filter: P) -> MillerBufferTimedFrames
+        where P: Fn(&Self) -> bool
+    {
+        let mut buffer_frames = MillerBufferTimedFrames::default();
+        let chunks = MillerTimesDown::from_raw(buffer, *config);
+        let iter_len = chunks.len();
+        for times_down in chunks.into_iter().skip(1).take(iter_len.saturating_sub(2)) {
+            if times_down.time_down_set.is_empty() {
+                continue;
+            }
+            let start = capture_start + times_down.start();
+            let end = capture_start + times_down.end();
+            match times_down.convert() {
+                Ok(frame) => {
+                    if frame_filter(&frame) {
+                        buffer_frames.frames.push(TimedFrame { frame, start, end })
+                    }
+                }
+                Err(error) => buffer_frames.errors.push(error),
+            }
+        }
+        buffer_frames
+    }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+/// Frames decoded from the buffer, and errors in chunks that were skipped.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct MillerBufferFrames {
+    pub frames: Vec<Frame>,
+    pub errors: Vec<MillerDecodeError>,
+}
+
+/// Timed frames decoded from the buffer, and errors in chunks that were
+/// skipped.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct MillerBufferTimedFrames {
+    pub frames: Vec<TimedFrame>,
+    pub errors: Vec<MillerDecodeError>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MillerElement {
     X,
     Y,
@@ -105,33 +186,177 @@ impl MillerCollector {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct MillerTimesDown<'a, const TICK_LEN: u16> {
+pub struct MillerTimesDown<'a> {
     time_down_set: &'a [u16],
+    config: DecoderConfig,
+    offset: usize,
+    chunk: usize,
+    start: u64,
 }
 
-impl<'a, const TICK_LEN: u16> MillerTimesDown<'a, TICK_LEN> {
+impl<'a> MillerTimesDown<'a> {
+    /// Times down of a single frame, with no idle gaps inside.
+    pub fn new(time_down_set: &'a [u16], config: DecoderConfig) -> Self {
+        Self {
+            time_down_set,
+            config,
+            offset: 0,
+            chunk: 0,
+            start: 0,
+        }
+    }
 
-    pub fn convert(self) -> Result<Frame, MillerError> {
+    /// Split raw times down record into frames at idle gaps.
+    pub fn from_raw(time_down_input: &'a [u16], config: DecoderConfig) -> Vec<Self> {
+        let mut offset = 0;
+        let mut start = 0;
+        time_down_input
+            .split(|interval| config.is_idle_down(*interval))
+            .enumerate()
+            .map(|(chunk, time_down_set)| {
+                let times_down = Self {
+                    time_down_set,
+                    config,
+                    offset,
+                    chunk,
+                    start,
+                };
+                // chunk and the idle gap after it
+                offset += time_down_set.len() + 1;
+                start = times_down.end()
+                    + time_down_input.get(offset - 1).map_or(0, |gap| *gap as u64);
+                times_down
+            })
+            .collect()
+    }
+
+    /// Time of the first pause, in timer counts from the first edge in the
+    /// record.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Time of the last pause, in timer counts from the first edge in the
+    /// record.
+    ///
+    /// This is the start of the last modulation in the frame, same as
+    /// [`TimesBoth::end`](crate::time_record_both_ways::TimesBoth::end) for
+    /// both-edge Miller record.
+    pub fn end(&self) -> u64 {
+        self.start
+            + self
+                .time_down_set
+                .iter()
+                .map(|interval| *interval as u64)
+                .sum::<u64>()
+    }
+
+    pub fn convert(self) -> Result<Frame, MillerDecodeError> {
         // each bit length is 8 ticks; expected error is 1 tick;
         // time intervals in off mode are identical throughout the code;
-        // no signal corresponds to 2 or more completely "on" bits, i.e. 16 ticks.
-        // since only times down are recorded, the selected slices are separated by 16+4 = 20 ticks.
-        // minimal 19 ticks are taken as the error is expected to be 1 tick;
-        // XYY (20 ticks) or ZYY (24 ticks)
+        // times down slices are separated at idle gaps, see `DecoderConfig`.
         let mut miller_element_set = MillerElementSet::new();
-        for time_interval in self.time_down_set.into_iter() {
-            miller_element_set.add_time_down_interval::<TICK_LEN>(*time_interval)?;
+        for (i, time_interval) in self.time_down_set.iter().enumerate() {
+            miller_element_set
+                .add_time_down_interval(*time_interval, &self.config)
+                .map_err(|error| miller_element_set.decode_error(error, self.offset + i, self.chunk))?;
         }
-        match miller_element_set.element_set.last() {
-            None => {},
-            Some(MillerElement::X) => {
-                miller_element_set.element_set.push(MillerElement::Y);
-                miller_element_set.element_set.push(MillerElement::Y);
-            },
-            Some(MillerElement::Y) => unreachable!(),
-            Some(MillerElement::Z) => miller_element_set.element_set.push(MillerElement::Y),
+        miller_element_set.add_end_of_frame();
+        miller_element_set.collect_frame().map_err(|error| {
+            miller_element_set.decode_error(error, self.offset + self.time_down_set.len(), self.chunk)
+        })
+    }
+}
+
+/// Push-based Miller decoder for continuous times down record.
+///
+/// Intervals could be pushed in chunks of any size, partial frame is kept
+/// between the calls. Decoder starts unsynchronized, i.e. everything before
+/// the first idle gap is skipped, as the record could start mid-frame.
+#[derive(Debug, Eq, PartialEq)]
+pub struct MillerStreamDecoder {
+    config: DecoderConfig,
+    synchronized: bool,
+    miller_element_set: MillerElementSet,
+    error: Option<MillerDecodeError>,
+    offset: usize,
+    chunk: usize,
+}
+
+impl MillerStreamDecoder {
+    pub fn new(config: DecoderConfig) -> Self {
+        Self {
+            config,
+            synchronized: false,
+            miller_element_set: MillerElementSet::new(),
+            error: None,
+            offset: 0,
+            chunk: 0,
+        }
+    }
+
+    /// Drop partial frame and wait for the next idle gap.
+    pub fn reset(&mut self) {
+        self.synchronized = false;
+        self.miller_element_set = MillerElementSet::new();
+        self.error = None;
+    }
+
+    /// Change timing parameters from the next interval on, e.g. after bit
+    /// rate change.
+    pub fn set_config(&mut self, config: DecoderConfig) {
+        self.config = config
+    }
+
+    /// Add single interval.
+    ///
+    /// Frame is completed when idle gap is received. After an error, the
+    /// remaining intervals of the frame are skipped and the error is returned
+    /// at the idle gap.
+    ///
+    /// Error positions are counted from the first interval pushed into the
+    /// decoder.
+    pub fn push_interval(&mut self, interval: u16) -> Option<Result<Frame, MillerDecodeError>> {
+        let offset = self.offset;
+        self.offset += 1;
+        if self.config.is_idle_down(interval) {
+            self.chunk += 1;
+            if !self.synchronized {
+                self.synchronized = true;
+                return None;
+            }
+            let mut miller_element_set = core::mem::take(&mut self.miller_element_set);
+            if let Some(error) = self.error.take() {
+                return Some(Err(error));
+            }
+            if miller_element_set.element_set.is_empty() {
+                return None;
+            }
+            miller_element_set.add_end_of_frame();
+            Some(
+                miller_element_set
+                    .collect_frame()
+                    .map_err(|error| miller_element_set.decode_error(error, offset, self.chunk - 1)),
+            )
+        } else {
+            if self.synchronized & self.error.is_none() {
+                if let Err(error) = self
+                    .miller_element_set
+                    .add_time_down_interval(interval, &self.config)
+                {
+                    self.error = Some(self.miller_element_set.decode_error(error, offset, self.chunk));
+                }
+            }
+            None
         }
-        miller_element_set.collect_frame()
+    }
+
+    /// Add a chunk of intervals, get all results completed in this chunk.
+    pub fn push(&mut self, intervals: &[u16]) -> Vec<Result<Frame, MillerDecodeError>> {
+        intervals
+            .iter()
+            .filter_map(|interval| self.push_interval(*interval))
+            .collect()
     }
 }
 
@@ -147,16 +372,17 @@ impl MillerElementSet {
         }
     }
 
-    fn process_previous_x<const TICK_LEN: u16>(
+    fn process_previous_x(
         &mut self,
         interval: u16,
+        config: &DecoderConfig,
     ) -> Result<(), MillerError> {
-        if (interval >= 7 * TICK_LEN) & (interval <= 9 * TICK_LEN) {
+        if config.matches(interval, 8) {
             self.element_set.push(MillerElement::X)
-        } else if (interval >= 11 * TICK_LEN) & (interval <= 13 * TICK_LEN) {
+        } else if config.matches(interval, 12) {
             self.element_set.push(MillerElement::Y);
             self.element_set.push(MillerElement::Z);
-        } else if (interval >= 15 * TICK_LEN) & (interval <= 17 * TICK_LEN) {
+        } else if config.matches(interval, 16) {
             self.element_set.push(MillerElement::Y);
             self.element_set.push(MillerElement::X);
         } else {
@@ -165,15 +391,16 @@ impl MillerElementSet {
         Ok(())
     }
 
-    fn process_previous_z<const TICK_LEN: u16>(
+    fn process_previous_z(
         &mut self,
         interval: u16,
+        config: &DecoderConfig,
     ) -> Result<(), MillerError> {
-        if (interval >= 7 * TICK_LEN) & (interval <= 9 * TICK_LEN) {
+        if config.matches(interval, 8) {
             self.element_set.push(MillerElement::Z)
-        } else if (interval >= 11 * TICK_LEN) & (interval <= 13 * TICK_LEN) {
+        } else if config.matches(interval, 12) {
             self.element_set.push(MillerElement::X)
-        } else if (interval >= 15 * TICK_LEN) & (interval <= 17 * TICK_LEN) {
+        } else if config.matches(interval, 16) {
             // sequence ZYZ is invalid and will be
             // sieved out during further processing
             self.element_set.push(MillerElement::Y);
@@ -184,30 +411,55 @@ impl MillerElementSet {
         Ok(())
     }
 
-    fn add_time_down_interval<const TICK_LEN: u16>(
+    pub(crate) fn decode_error(&self, error: MillerError, offset: usize, chunk: usize) -> MillerDecodeError {
+        MillerDecodeError {
+            error,
+            position: ErrorPosition::new(offset, chunk, &self.element_set),
+        }
+    }
+
+    /// Times down record has no edges after the last pause, the elements
+    /// that complete the frame are added here.
+    fn add_end_of_frame(&mut self) {
+        match self.element_set.last() {
+            None => {}
+            Some(MillerElement::X) => {
+                self.element_set.push(MillerElement::Y);
+                self.element_set.push(MillerElement::Y);
+            }
+            Some(MillerElement::Y) => unreachable!(),
+            Some(MillerElement::Z) => self.element_set.push(MillerElement::Y),
+        }
+    }
+
+    fn add_time_down_interval(
         &mut self,
         interval: u16,
+        config: &DecoderConfig,
     ) -> Result<(), MillerError> {
-        // intervals above 20 ticks are expected to be eliminated at this point
+        // idle gaps are expected to be eliminated at this point
         match self.element_set.last() {
             None => {
                 self.element_set.push(MillerElement::Z);
-                self.process_previous_z::<TICK_LEN>(interval)
+                self.process_previous_z(interval, config)
             }
-            Some(MillerElement::X) => self.process_previous_x::<TICK_LEN>(interval),
+            Some(MillerElement::X) => self.process_previous_x(interval, config),
             Some(MillerElement::Y) => unreachable!(),
-            Some(MillerElement::Z) => self.process_previous_z::<TICK_LEN>(interval),
+            Some(MillerElement::Z) => self.process_previous_z(interval, config),
         }
     }
-/*
-    pub(crate) fn add_time_both_interval<const TICK_LEN: u16>(
+
+    /// Pause (`first_len`) is checked on its own, pause together with the
+    /// following gap (`second_len`) gives the distance to the next pause.
+    pub(crate) fn add_time_both_interval(
         &mut self,
         time_both: EntryTimesBoth,
+        config: &DecoderConfig,
     ) -> Result<(), MillerError> {
-        if (time_both.first_len < 3 * TICK_LEN) | (time_both.first_len > 5 * TICK_LEN) {
+        if !config.is_pause(time_both.first_len) {
             return Err(MillerError::UnexpectedMillerOffInterval(time_both.first_len));
         }
-        // intervals above 16 ticks are expected to be eliminated at this point
+        // idle gaps are expected to be eliminated at this point
         match self.element_set.last() {
             None => {
                 self.element_set.push(MillerElement::Z);
@@ -218,20 +470,24 @@ impl MillerElementSet {
                     }
                     Some(second_len) => {
                         let interval = second_len + time_both.first_len;
-                        self.process_previous_z::<TICK_LEN>(interval)
+                        self.process_previous_z(interval, config)
                     }
                 }
             }
             Some(MillerElement::X) => {
                 match time_both.second_len {
                     None => {
-                        // XY is invalid final sequence, is filtered out on further processing
+                        // XY is invalid final sequence, is filtered out on further processing,
+                        // unless logic 0 and end of frame are assumed
                         self.element_set.push(MillerElement::Y);
+                        if config.accept_trailing_x {
+                            self.element_set.push(MillerElement::Y);
+                        }
                         Ok(())
                     }
                     Some(second_len) => {
                         let interval = second_len + time_both.first_len;
-                        self.process_previous_x::<TICK_LEN>(interval)
+                        self.process_previous_x(interval, config)
                     }
                 }
             }
@@ -243,21 +499,77 @@ impl MillerElementSet {
                 }
                 Some(second_len) => {
                     let interval = second_len + time_both.first_len;
-                    self.process_previous_z::<TICK_LEN>(interval)
+                    self.process_previous_z(interval, config)
                 }
             },
         }
     }
+
     pub fn from_times_both<const TICK_LEN: u16>(
         times_both: SetTimesBoth<TICK_LEN>,
-    ) -> Result<Self, MillerError> {
+    ) -> Result<Self, MillerDecodeError> {
         times_both.convert_to_miller()
     }
-*/
-    pub fn collect_frame(self) -> Result<Frame, MillerError> {
+
+    /// Elements for transmitting the bits, with start and end of frame.
+    ///
+    /// Start of frame is Z. Logic 1 is X. Logic 0 is Z if it follows start
+    /// of frame or logic 0, and Y otherwise. End of frame is logic 0
+    /// followed by Y.
+    pub fn from_bits(bits: &BitSlice<u8, Lsb0>) -> Self {
+        let mut miller_element_set = Self::new();
+        miller_element_set.element_set.push(MillerElement::Z);
+        let mut last_bit = false;
+        for bit in bits.iter().by_vals().chain([false]) {
+            if bit {
+                miller_element_set.element_set.push(MillerElement::X)
+            } else if last_bit {
+                miller_element_set.element_set.push(MillerElement::Y)
+            } else {
+                miller_element_set.element_set.push(MillerElement::Z)
+            }
+            last_bit = bit;
+        }
+        miller_element_set.element_set.push(MillerElement::Y);
+        miller_element_set
+    }
+
+    pub fn from_frame(frame: &Frame) -> Self {
+        Self::from_bits(&frame.to_bits())
+    }
+
+    /// Intervals between pause starts, i.e. times down record of the frame.
+    ///
+    /// Z pause is at the beginning of bit period, X pause is in the middle,
+    /// Y has no pause. Start of frame pause is not preceded by an interval.
+    ///
+    /// Fails if an interval in timer counts does not fit in `u16`.
+    pub fn to_times_down<const TICK_LEN: u16>(&self) -> Result<Vec<u16>, MillerError> {
+        self.to_times_down_with_config(&DecoderConfig::new(TICK_LEN))
+    }
+
+    pub fn to_times_down_with_config(&self, config: &DecoderConfig) -> Result<Vec<u16>, MillerError> {
+        let mut times_down = Vec::new();
+        let mut last_pause: Option<u64> = None;
+        for (i, element) in self.element_set.iter().enumerate() {
+            let pause = match element {
+                MillerElement::X => 8 * i as u64 + 4,
+                MillerElement::Y => continue,
+                MillerElement::Z => 8 * i as u64,
+            };
+            if let Some(last_pause) = last_pause {
+                let interval = config.ticks_to_counts(pause - last_pause);
+                times_down.push(u16::try_from(interval).map_err(|_| MillerError::IntervalOverflow)?);
+            }
+            last_pause = Some(pause);
+        }
+        Ok(times_down)
+    }
+
+    pub fn collect_frame(&self) -> Result<Frame, MillerError> {
         let mut collector = MillerCollector::Empty;
-        for element in self.element_set.into_iter() {
-            collector.add_element(element)?;
+        for element in self.element_set.iter() {
+            collector.add_element(*element)?;
         }
         if let MillerCollector::Complete(complete_collector) = collector {
             Ok(complete_collector)
@@ -272,3 +584,278 @@ impl Default for MillerElementSet {
         Self::new()
     }
 }
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_record_both_ways::TimesBoth;
+
+    #[test]
+    fn miller_time_both_1() {
+        let times_set = [
+            25001, 82, 101, 75, 191, 80, 102, 75, 191, 79, 189, 80, 189, 80, 1734,
+        ];
+        let chunk = SetTimesBoth::<22u16>::from_raw(&times_set).remove(0);
+        let miller_element_set = MillerElementSet::from_times_both(chunk).unwrap();
+        let frame = miller_element_set.collect_frame().unwrap();
+        assert_eq!(frame, Frame::Short(0x26));
+    }
+
+    #[test]
+    fn miller_time_both_2() {
+        let times_set = [
+            58364, 72, 110, 68, 198, 71, 198, 71, 198, 71, 109, 68, 289, 71, 110, 68, 111, 68, 110,
+            68, 111, 68, 198, 71, 198, 71, 109, 69, 198, 71, 198, 71, 110, 68, 110, 68, 199, 71,
+            110, 68, 199, 70, 110, 68, 199, 71, 1737,
+        ];
+        let chunk = SetTimesBoth::<22u16>::from_raw(&times_set).remove(0);
+        let miller_element_set = MillerElementSet::from_times_both(chunk).unwrap();
+        let frame = miller_element_set.collect_frame().unwrap();
+        assert_eq!(frame, Frame::Standard(vec![0xB2]));
+    }
+
+    #[test]
+    fn miller_time_down() {
+        let times_set = [187, 266, 269, 269, 359, 1894];
+        let chunk = MillerTimesDown::from_raw(&times_set, DecoderConfig::new(22)).remove(0);
+        let frame = chunk.convert().unwrap();
+        assert_eq!(frame, Frame::Short(0x52));
+    }
+
+    #[test]
+    fn miller_time_down_other_clock() {
+        // same as `miller_time_down`, recorded with timer running twice as fast
+        let times_set = [374, 532, 538, 538, 718, 3788];
+        let chunk = MillerTimesDown::from_raw(&times_set, DecoderConfig::new(44)).remove(0);
+        let frame = chunk.convert().unwrap();
+        assert_eq!(frame, Frame::Short(0x52));
+    }
+
+    #[test]
+    fn miller_time_down_tolerance() {
+        let times_set = [187, 266, 269, 269, 359, 1894];
+        let config = DecoderConfig {
+            tolerance: 0,
+            ..DecoderConfig::new(22)
+        };
+        let chunk = MillerTimesDown::from_raw(&times_set, config).remove(0);
+        assert_eq!(
+            chunk.convert(),
+            Err(MillerDecodeError {
+                error: MillerError::UnexpectedInterval(187),
+                position: ErrorPosition {
+                    offset: 0,
+                    chunk: 0,
+                    bits: 0,
+                    last_elements: vec![MillerElement::Z],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn miller_buffer_tolerant() {
+        let times_set = [
+            266, 1894, 187, 60, 269, 1894, 187, 266, 269, 269, 359, 1894, 1894, 187, 266,
+            269, 269, 359, 1894, 187,
+        ];
+        let config = DecoderConfig::new(22);
+        assert_eq!(
+            Frame::process_buffer_miller_skip_tails_with_config(&times_set, &config, |_| true),
+            Vec::new()
+        );
+        assert_eq!(
+            Frame::process_buffer_miller_skip_tails_tolerant(&times_set, &config, |_| true),
+            MillerBufferFrames {
+                frames: vec![Frame::Short(0x52), Frame::Short(0x52)],
+                errors: vec![MillerDecodeError {
+                    error: MillerError::UnexpectedInterval(60),
+                    position: ErrorPosition {
+                        offset: 3,
+                        chunk: 1,
+                        bits: 1,
+                        last_elements: vec![MillerElement::Z, MillerElement::Z],
+                    },
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn miller_buffer_timed() {
+        // same as `miller_buffer_tolerant`
+        let times_set = [
+            266, 1894, 187, 60, 269, 1894, 187, 266, 269, 269, 359, 1894, 1894, 187, 266,
+            269, 269, 359, 1894, 187,
+        ];
+        let config = DecoderConfig::new(22);
+        let timed_frames = Frame::process_buffer_miller_timed(&times_set, &config, 100_000, |_| true);
+        assert_eq!(timed_frames.errors.len(), 1);
+        assert_eq!(timed_frames.errors[0].position.chunk, 1);
+        let timed_frames = timed_frames.frames;
+        assert_eq!(
+            timed_frames,
+            vec![
+                TimedFrame {
+                    frame: Frame::Short(0x52),
+                    start: 104_570,
+                    end: 105_920,
+                },
+                TimedFrame {
+                    frame: Frame::Short(0x52),
+                    start: 109_708,
+                    end: 111_058,
+                },
+            ]
+        );
+        assert_eq!(timed_frames[0].duration(), 1350);
+    }
+
+    #[test]
+    fn miller_generate_1() {
+        let miller_element_set = MillerElementSet::from_frame(&Frame::Short(0x52));
+        assert_eq!(
+            miller_element_set.element_set,
+            vec![
+                MillerElement::Z,
+                MillerElement::Z,
+                MillerElement::X,
+                MillerElement::Y,
+                MillerElement::Z,
+                MillerElement::X,
+                MillerElement::Y,
+                MillerElement::X,
+                MillerElement::Y,
+                MillerElement::Y,
+            ]
+        );
+        assert_eq!(
+            miller_element_set.to_times_down::<22u16>().unwrap(),
+            vec![176, 264, 264, 264, 352]
+        );
+        assert_eq!(miller_element_set.collect_frame(), Ok(Frame::Short(0x52)));
+        assert_eq!(
+            miller_element_set.to_times_down::<10000u16>(),
+            Err(MillerError::IntervalOverflow)
+        );
+        let long_frame = MillerElementSet::from_frame(&Frame::Standard(vec![0x00; 1000]));
+        assert!(long_frame.to_times_down::<22u16>().is_ok());
+    }
+
+    #[test]
+    fn miller_generate_2() {
+        let frames = [
+            Frame::Short(0x26),
+            Frame::Standard(vec![0x50, 0x00]),
+            Frame::SddCleanCut(vec![0x93, 0x20]),
+            Frame::Standard(vec![0x93, 0x70, 0x01, 0x02, 0x03, 0x04, 0x04]),
+        ];
+        let mut times_set = vec![1894];
+        for frame in frames.iter() {
+            let miller_element_set = MillerElementSet::from_frame(frame);
+            assert_eq!(miller_element_set.collect_frame().as_ref(), Ok(frame));
+            times_set.extend(miller_element_set.to_times_down::<22u16>().unwrap());
+            times_set.push(1894);
+        }
+        assert_eq!(
+            Frame::process_buffer_miller_skip_tails::<_, 22u16>(&times_set, |_| true),
+            frames
+        );
+    }
+
+    #[test]
+    fn miller_stream() {
+        let mut decoder = MillerStreamDecoder::new(DecoderConfig::new(22));
+        assert!(decoder.push(&[266, 269, 1894, 187, 266]).is_empty());
+        assert_eq!(
+            decoder.push(&[269, 269, 359, 1894, 187]),
+            vec![Ok(Frame::Short(0x52))]
+        );
+        assert_eq!(
+            decoder.push(&[266, 269, 269, 359, 1894, 1894]),
+            vec![Ok(Frame::Short(0x52))]
+        );
+    }
+
+    #[test]
+    fn miller_stream_single_intervals() {
+        let times_set = [1894, 187, 266, 269, 269, 359, 1894];
+        let mut decoder = MillerStreamDecoder::new(DecoderConfig::new(22));
+        let mut frames = Vec::new();
+        for interval in times_set {
+            if let Some(result) = decoder.push_interval(interval) {
+                frames.push(result.unwrap());
+            }
+        }
+        assert_eq!(frames, vec![Frame::Short(0x52)]);
+    }
+
+    #[test]
+    fn miller_stream_recovers() {
+        let mut decoder = MillerStreamDecoder::new(DecoderConfig::new(22));
+        assert_eq!(
+            decoder.push(&[1894, 187, 60, 269, 1894, 187, 266, 269, 269, 359, 1894]),
+            vec![
+                Err(MillerDecodeError {
+                    error: MillerError::UnexpectedInterval(60),
+                    position: ErrorPosition {
+                        offset: 2,
+                        chunk: 1,
+                        bits: 1,
+                        last_elements: vec![MillerElement::Z, MillerElement::Z],
+                    },
+                }),
+                Ok(Frame::Short(0x52))
+            ]
+        );
+    }
+
+    #[test]
+    fn miller_time_both_short_pause() {
+        // same as `miller_time_both_1`, but the second pause is a glitch
+        let times_set = [
+            25001, 82, 101, 30, 236, 80, 102, 75, 191, 79, 189, 80, 189, 80, 1734,
+        ];
+        let chunk = SetTimesBoth::<22u16>::from_raw(&times_set).remove(0);
+        let error = MillerElementSet::from_times_both(chunk).unwrap_err();
+        assert_eq!(error.error, MillerError::UnexpectedMillerOffInterval(30));
+        assert_eq!(error.position.offset, 3);
+        assert_eq!(error.position.chunk, 1);
+    }
+
+    #[test]
+    fn miller_time_both_long_pause() {
+        // same as `miller_time_both_1`, but the second pause is too long
+        let times_set = [
+            25001, 82, 101, 130, 136, 80, 102, 75, 191, 79, 189, 80, 189, 80, 1734,
+        ];
+        let chunk = SetTimesBoth::<22u16>::from_raw(&times_set).remove(0);
+        let error = MillerElementSet::from_times_both(chunk).unwrap_err();
+        assert_eq!(error.error, MillerError::UnexpectedMillerOffInterval(130));
+        assert_eq!(error.position.offset, 3);
+        assert_eq!(error.position.chunk, 1);
+    }
+
+    #[test]
+    fn miller_time_both_trailing_x() {
+        // Short(0x40), last pause is X
+        let times_set = [
+            25001, 80, 96, 80, 96, 80, 96, 80, 96, 80, 96, 80, 96, 80, 184, 80, 1734,
+        ];
+        let chunk = &TimesBoth::from_raw(&times_set, DecoderConfig::new(22))[0];
+        let miller_element_set = chunk.convert_to_miller().unwrap();
+        assert_eq!(
+            miller_element_set.collect_frame(),
+            Err(MillerError::IncompleteFrame)
+        );
+
+        let config = DecoderConfig {
+            accept_trailing_x: true,
+            ..DecoderConfig::new(22)
+        };
+        let chunk = &TimesBoth::from_raw(&times_set, config)[0];
+        let miller_element_set = chunk.convert_to_miller().unwrap();
+        assert_eq!(miller_element_set.collect_frame(), Ok(Frame::Short(0x40)));
+    }
+}
@@ -0,0 +1,322 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::frame::{Frame, FrameAttributed};
+use crate::iso14443_3::{PcdCommand, PiccResponse, SAK_CASCADE_BIT};
+
+/// PCB of S(DESELECT) block, with CID bit cleared.
+const S_DESELECT: u8 = 0xC2;
+
+/// PICC state, as defined in ISO 14443-3.
+///
+/// `ReadyStar` and `ActiveStar` are READY* and ACTIVE*, entered from HALT
+/// with WUPA; PICC goes back to HALT from these. `Protocol` is ISO 14443-4
+/// protocol state, entered with RATS.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PiccState {
+    Idle,
+    Ready,
+    Active,
+    Halt,
+    ReadyStar,
+    ActiveStar,
+    Protocol,
+}
+
+impl PiccState {
+    /// State to go to on unexpected command.
+    fn fallback(&self) -> Self {
+        match self {
+            PiccState::ReadyStar | PiccState::ActiveStar | PiccState::Halt => PiccState::Halt,
+            _ => PiccState::Idle,
+        }
+    }
+}
+
+/// Answer expected from PICC to the last PCD command.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Expected {
+    Atqa,
+    Uid,
+    Sak,
+    Ats,
+    Any,
+}
+
+impl Expected {
+    fn matches(&self, response: &PiccResponse) -> bool {
+        matches!(
+            (self, response),
+            (Expected::Atqa, PiccResponse::Atqa(_))
+                | (Expected::Uid, PiccResponse::Uid { .. })
+                | (Expected::Sak, PiccResponse::Sak(_))
+                | (Expected::Ats, PiccResponse::Ats(_))
+                | (Expected::Any, _)
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ViolationKind {
+    /// PCD command not allowed in PICC state, e.g. SELECT without REQA.
+    UnexpectedCommand(PcdCommand),
+    /// PICC frame when PICC must stay silent, e.g. answer in HALT.
+    UnexpectedResponse(PiccResponse),
+    /// PICC answer does not fit the command, e.g. SAK to ANTICOLLISION.
+    WrongResponse {
+        command: PcdCommand,
+        response: PiccResponse,
+    },
+}
+
+/// Protocol violation found in the session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateViolation {
+    /// Index of the frame in the session.
+    pub frame: usize,
+    /// PICC state before the frame.
+    pub state: PiccState,
+    pub kind: ViolationKind,
+}
+
+/// Replays a session through ISO 14443-3 PICC state model.
+///
+/// Single PICC in the field is assumed, starting in IDLE state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PiccStateTracker {
+    state: PiccState,
+    /// Last PCD command and answer expected to it, until PICC answers.
+    pending: Option<(PcdCommand, Expected)>,
+    frame: usize,
+    violations: Vec<StateViolation>,
+}
+
+impl PiccStateTracker {
+    pub fn new() -> Self {
+        Self {
+            state: PiccState::Idle,
+            pending: None,
+            frame: 0,
+            violations: Vec::new(),
+        }
+    }
+
+    pub fn state(&self) -> PiccState {
+        self.state
+    }
+
+    pub fn violations(&self) -> &[StateViolation] {
+        &self.violations
+    }
+
+    fn violation(&mut self, kind: ViolationKind) {
+        self.violations.push(StateViolation {
+            frame: self.frame,
+            state: self.state,
+            kind,
+        })
+    }
+
+    pub fn add_command(&mut self, command: &PcdCommand) {
+        let (state, expected) = match (self.state, command) {
+            (PiccState::Idle, PcdCommand::Reqa | PcdCommand::Wupa) => {
+                (PiccState::Ready, Some(Expected::Atqa))
+            }
+            (PiccState::Halt, PcdCommand::Wupa) => (PiccState::ReadyStar, Some(Expected::Atqa)),
+            (PiccState::Idle | PiccState::Halt, PcdCommand::Reqa | PcdCommand::Hlta) => {
+                (self.state, None)
+            }
+            (PiccState::Idle | PiccState::Halt, _) => {
+                self.violation(ViolationKind::UnexpectedCommand(command.clone()));
+                (self.state, None)
+            }
+            (PiccState::Ready | PiccState::ReadyStar, PcdCommand::Anticollision { .. }) => {
+                (self.state, Some(Expected::Uid))
+            }
+            (PiccState::Ready | PiccState::ReadyStar, PcdCommand::Select { .. }) => {
+                (self.state, Some(Expected::Sak))
+            }
+            (PiccState::Ready | PiccState::ReadyStar, PcdCommand::Rats { .. })
+            | (PiccState::Ready | PiccState::ReadyStar, PcdCommand::Unknown(_)) => {
+                self.violation(ViolationKind::UnexpectedCommand(command.clone()));
+                (self.state.fallback(), None)
+            }
+            (PiccState::Active | PiccState::ActiveStar, PcdCommand::Hlta) => {
+                (PiccState::Halt, None)
+            }
+            (PiccState::Active | PiccState::ActiveStar, PcdCommand::Rats { .. }) => {
+                (PiccState::Protocol, Some(Expected::Ats))
+            }
+            (PiccState::Active | PiccState::ActiveStar, PcdCommand::Unknown(_)) => {
+                (self.state, Some(Expected::Any))
+            }
+            (PiccState::Protocol, PcdCommand::Unknown(Frame::Standard(data)))
+                if data.first().is_some_and(|pcb| pcb & 0xF7 == S_DESELECT) =>
+            {
+                (PiccState::Halt, Some(Expected::Any))
+            }
+            (PiccState::Protocol, PcdCommand::Unknown(_)) => {
+                (PiccState::Protocol, Some(Expected::Any))
+            }
+            (PiccState::Protocol, PcdCommand::Rats { .. }) => {
+                self.violation(ViolationKind::UnexpectedCommand(command.clone()));
+                (PiccState::Protocol, None)
+            }
+            // any other command sends PICC back to IDLE or HALT
+            _ => (self.state.fallback(), None),
+        };
+        self.state = state;
+        self.pending = expected.map(|expected| (command.clone(), expected));
+        self.frame += 1;
+    }
+
+    pub fn add_response(&mut self, response: &PiccResponse) {
+        match self.pending.take() {
+            None => self.violation(ViolationKind::UnexpectedResponse(response.clone())),
+            Some((command, expected)) => {
+                if !expected.matches(response) {
+                    self.violation(ViolationKind::WrongResponse {
+                        command,
+                        response: response.clone(),
+                    })
+                } else if let (PcdCommand::Select { .. }, PiccResponse::Sak(sak)) =
+                    (command, response)
+                {
+                    if sak & SAK_CASCADE_BIT == 0 {
+                        self.state = match self.state {
+                            PiccState::ReadyStar => PiccState::ActiveStar,
+                            _ => PiccState::Active,
+                        }
+                    }
+                }
+            }
+        }
+        self.frame += 1;
+    }
+
+    pub fn add_frame(&mut self, frame: &FrameAttributed) {
+        match frame {
+            FrameAttributed::Miller(frame) => self.add_command(&PcdCommand::from_frame(frame)),
+            FrameAttributed::Manchester(frame) => {
+                let response = match &self.pending {
+                    Some((command, _)) => PiccResponse::from_frame_after(frame, command),
+                    None => PiccResponse::from_frame(frame),
+                };
+                self.add_response(&response)
+            }
+        }
+    }
+}
+
+impl Default for PiccStateTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iso14443_3::{CascadeLevel, Nvb};
+
+    #[test]
+    fn session_clean() {
+        let session = [
+            FrameAttributed::Miller(Frame::Short(0x26)),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x44, 0x00])),
+            FrameAttributed::Miller(Frame::SddCleanCut(vec![0x93, 0x20])),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x88, 0x04, 0x21, 0x32, 0x9F])),
+            FrameAttributed::Miller(Frame::Standard(vec![
+                0x93, 0x70, 0x88, 0x04, 0x21, 0x32, 0x9F,
+            ])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x04])),
+            FrameAttributed::Miller(Frame::SddCleanCut(vec![0x95, 0x20])),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x4A, 0x5B, 0x6C, 0x80, 0xFD])),
+            FrameAttributed::Miller(Frame::Standard(vec![
+                0x95, 0x70, 0x4A, 0x5B, 0x6C, 0x80, 0xFD,
+            ])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x20])),
+            FrameAttributed::Miller(Frame::Standard(vec![0xE0, 0x80])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x05, 0x78, 0x80, 0x70, 0x02])),
+            FrameAttributed::Miller(Frame::Standard(vec![0xC2])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0xC2])),
+            FrameAttributed::Miller(Frame::Short(0x52)),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x44, 0x00])),
+        ];
+        let mut tracker = PiccStateTracker::new();
+        let mut states = Vec::new();
+        for frame in session.iter() {
+            tracker.add_frame(frame);
+            states.push(tracker.state());
+        }
+        assert_eq!(tracker.violations(), &[]);
+        assert_eq!(states[5], PiccState::Ready);
+        assert_eq!(states[9], PiccState::Active);
+        assert_eq!(states[11], PiccState::Protocol);
+        assert_eq!(states[13], PiccState::Halt);
+        assert_eq!(tracker.state(), PiccState::ReadyStar);
+    }
+
+    #[test]
+    fn session_violations() {
+        let session = [
+            // SELECT with no REQA before it
+            FrameAttributed::Miller(Frame::Standard(vec![
+                0x93, 0x70, 0xDE, 0xAD, 0xBE, 0xEF, 0x22,
+            ])),
+            FrameAttributed::Miller(Frame::Short(0x26)),
+            FrameAttributed::Manchester(Frame::SddCleanCut(vec![0x04, 0x00])),
+            FrameAttributed::Miller(Frame::SddCleanCut(vec![0x93, 0x20])),
+            // SAK to ANTICOLLISION
+            FrameAttributed::Manchester(Frame::Standard(vec![0x08])),
+            FrameAttributed::Miller(Frame::Standard(vec![
+                0x93, 0x70, 0xDE, 0xAD, 0xBE, 0xEF, 0x22,
+            ])),
+            FrameAttributed::Manchester(Frame::Standard(vec![0x08])),
+            FrameAttributed::Miller(Frame::Standard(vec![0x50, 0x00])),
+            // answer in HALT
+            FrameAttributed::Manchester(Frame::Standard(vec![0x0A, 0x00])),
+        ];
+        let mut tracker = PiccStateTracker::new();
+        for frame in session.iter() {
+            tracker.add_frame(frame);
+        }
+        assert_eq!(tracker.state(), PiccState::Halt);
+        assert_eq!(
+            tracker.violations(),
+            &[
+                StateViolation {
+                    frame: 0,
+                    state: PiccState::Idle,
+                    kind: ViolationKind::UnexpectedCommand(PcdCommand::Select {
+                        level: CascadeLevel::One,
+                        uid: [0xDE, 0xAD, 0xBE, 0xEF],
+                        bcc: 0x22,
+                    }),
+                },
+                StateViolation {
+                    frame: 4,
+                    state: PiccState::Ready,
+                    kind: ViolationKind::WrongResponse {
+                        command: PcdCommand::Anticollision {
+                            level: CascadeLevel::One,
+                            nvb: Nvb { bytes: 2, bits: 0 },
+                            uid_bits: vec![],
+                        },
+                        response: PiccResponse::SingleByte(0x08),
+                    },
+                },
+                StateViolation {
+                    frame: 8,
+                    state: PiccState::Halt,
+                    kind: ViolationKind::UnexpectedResponse(PiccResponse::Unknown(
+                        Frame::Standard(vec![0x0A, 0x00])
+                    )),
+                },
+            ]
+        );
+    }
+}
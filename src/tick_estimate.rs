@@ -0,0 +1,243 @@
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use crate::config::DecoderConfig;
+
+/// Nominal interval lengths, in ticks, in Miller times-down record.
+const MILLER_CLUSTERS: [u16; 3] = [8, 12, 16];
+
+/// Nominal interval lengths, in ticks, in Manchester both-edge record.
+const MANCHESTER_CLUSTERS: [u16; 3] = [4, 8, 12];
+
+/// Coding assumed for the capture during estimation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Coding {
+    /// Modified Miller, times down only.
+    Miller,
+    /// Manchester, both edges.
+    Manchester,
+}
+
+impl Coding {
+    fn clusters(&self) -> &'static [u16] {
+        match self {
+            Coding::Miller => &MILLER_CLUSTERS,
+            Coding::Manchester => &MANCHESTER_CLUSTERS,
+        }
+    }
+}
+
+/// Most likely tick length for a capture.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TickEstimate {
+    pub tick_len: u16,
+    pub coding: Coding,
+    /// Part of all intervals in the capture that fall into the expected
+    /// clusters with this tick length, from 0 to 1.
+    pub confidence: f32,
+}
+
+impl TickEstimate {
+    /// Default decoder config for estimated tick length.
+    pub fn config(&self) -> DecoderConfig {
+        DecoderConfig::new(self.tick_len)
+    }
+}
+
+/// Interval histogram of a raw capture.
+#[derive(Debug, Eq, PartialEq)]
+pub struct IntervalHistogram {
+    counts: BTreeMap<u16, usize>,
+    total: usize,
+}
+
+impl IntervalHistogram {
+    pub fn from_raw(buffer: &[u16]) -> Self {
+        let mut counts = BTreeMap::new();
+        for interval in buffer.iter() {
+            *counts.entry(*interval).or_insert(0) += 1;
+        }
+        Self {
+            counts,
+            total: buffer.len(),
+        }
+    }
+
+    /// Number of intervals in the capture.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Number of intervals matching expected clusters of `coding` with given
+    /// tick length.
+    fn matched(&self, coding: Coding, tick_len: u16) -> usize {
+        let config = DecoderConfig::new(tick_len);
+        self.counts
+            .iter()
+            .filter(|(interval, _)| {
+                coding
+                    .clusters()
+                    .iter()
+                    .any(|ticks| config.matches(**interval, *ticks))
+            })
+            .map(|(_, count)| *count)
+            .sum()
+    }
+
+    /// Tick length from the average of all intervals matched with `tick_len`,
+    /// each divided by the nominal length of its cluster.
+    fn refine(&self, coding: Coding, tick_len: u16) -> u16 {
+        let config = DecoderConfig::new(tick_len);
+        let mut counts_sum: u64 = 0;
+        let mut ticks_sum: u64 = 0;
+        for (interval, count) in self.counts.iter() {
+            if let Some(ticks) = coding
+                .clusters()
+                .iter()
+                .find(|ticks| config.matches(*interval, **ticks))
+            {
+                counts_sum += *interval as u64 * *count as u64;
+                ticks_sum += *ticks as u64 * *count as u64;
+            }
+        }
+        (counts_sum + ticks_sum / 2)
+            .checked_div(ticks_sum)
+            .map_or(tick_len, |refined| refined as u16)
+    }
+
+    fn estimate_with_tick(&self, coding: Coding, tick_len: u16) -> TickEstimate {
+        let confidence = if self.total == 0 {
+            0.0
+        } else {
+            self.matched(coding, tick_len) as f32 / self.total as f32
+        };
+        TickEstimate {
+            tick_len,
+            coding,
+            confidence,
+        }
+    }
+
+    /// Most likely tick length, assuming given coding.
+    ///
+    /// Each interval in the histogram is tried as a member of each expected
+    /// cluster; the candidate tick length that puts most intervals into the
+    /// clusters is then refined on the matched intervals.
+    pub fn estimate_for(&self, coding: Coding) -> Option<TickEstimate> {
+        let mut candidates: BTreeSet<u16> = BTreeSet::new();
+        for interval in self.counts.keys() {
+            for ticks in coding.clusters().iter() {
+                let tick_len = ((*interval as u32 + *ticks as u32 / 2) / *ticks as u32) as u16;
+                if tick_len != 0 {
+                    candidates.insert(tick_len);
+                }
+            }
+        }
+        let mut best: Option<TickEstimate> = None;
+        for tick_len in candidates.into_iter() {
+            let estimate = self.estimate_with_tick(coding, tick_len);
+            match best {
+                Some(ref current) if current.confidence >= estimate.confidence => {}
+                _ => best = Some(estimate),
+            }
+        }
+        best.map(|rough| {
+            let refined = self.estimate_with_tick(coding, self.refine(coding, rough.tick_len));
+            if refined.confidence >= rough.confidence {
+                refined
+            } else {
+                rough
+            }
+        })
+        .filter(|estimate| estimate.confidence > 0.0)
+    }
+
+    /// Most likely tick length and coding.
+    pub fn estimate(&self) -> Option<TickEstimate> {
+        match (
+            self.estimate_for(Coding::Miller),
+            self.estimate_for(Coding::Manchester),
+        ) {
+            (Some(miller), Some(manchester)) => {
+                if manchester.confidence > miller.confidence {
+                    Some(manchester)
+                } else {
+                    Some(miller)
+                }
+            }
+            (Some(miller), None) => Some(miller),
+            (None, manchester) => manchester,
+        }
+    }
+}
+
+/// Estimate tick length of a raw capture, with no prior knowledge of timer clock.
+pub fn estimate_tick_len(buffer: &[u16]) -> Option<TickEstimate> {
+    IntervalHistogram::from_raw(buffer).estimate()
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::Frame;
+    use crate::time_record_both_ways::TimesBoth;
+    use std::vec::Vec;
+
+    const MANCHESTER_CAPTURE: [u16; 45] = [
+        1740, 97, 82, 97, 80, 176, 94, 96, 82, 98, 167, 180, 179, 102, 81, 97, 81, 98, 81, 97,
+        81, 98, 81, 176, 178, 102, 82, 175, 179, 181, 178, 102, 80, 177, 93, 98, 80, 98, 167,
+        101, 82, 97, 82, 256, 28703,
+    ];
+
+    #[test]
+    fn empty() {
+        assert!(estimate_tick_len(&[]).is_none());
+    }
+
+    #[test]
+    fn no_overflow() {
+        assert!(estimate_tick_len(&[100, u16::MAX]).is_some());
+    }
+
+    #[test]
+    fn miller_times_down() {
+        let times_set = [
+            1894, 187, 266, 269, 269, 359, 1894, 187, 266, 269, 269, 359, 1894,
+        ];
+        let estimate = IntervalHistogram::from_raw(&times_set)
+            .estimate_for(Coding::Miller)
+            .unwrap();
+        assert!((22..=23).contains(&estimate.tick_len));
+        assert!(estimate.confidence > 0.7);
+        let frames = Frame::process_buffer_miller_skip_tails_with_config(
+            &times_set,
+            &estimate.config(),
+            |_| true,
+        );
+        assert_eq!(frames, vec![Frame::Short(0x52), Frame::Short(0x52)]);
+    }
+
+    #[test]
+    fn manchester_both() {
+        let estimate = estimate_tick_len(&MANCHESTER_CAPTURE).unwrap();
+        assert_eq!(estimate.coding, Coding::Manchester);
+        assert!((21..=23).contains(&estimate.tick_len));
+        assert!(estimate.confidence > 0.9);
+    }
+
+    #[test]
+    fn manchester_both_other_clock() {
+        let times_set: Vec<u16> = MANCHESTER_CAPTURE
+            .iter()
+            .map(|x| (*x as u32 * 3 / 2) as u16)
+            .collect();
+        let estimate = estimate_tick_len(&times_set).unwrap();
+        assert_eq!(estimate.coding, Coding::Manchester);
+        let chunk = &TimesBoth::from_raw(&times_set, estimate.config())[0];
+        let frame = chunk.convert_to_manchester().unwrap().collect_frame().unwrap();
+        assert_eq!(frame, Frame::Standard(vec![0xA3]));
+    }
+}
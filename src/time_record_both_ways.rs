@@ -3,9 +3,11 @@ use std::vec::Vec;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use crate::error::{ManchesterError, MillerError};
+use crate::config::DecoderConfig;
+use crate::error::{ManchesterDecodeError, ManchesterError, MillerDecodeError, MillerError};
+use crate::frame::{Frame, TimedFrame};
 use crate::manchester::ManchesterElementSet;
-use crate::miller::MillerElementSet;
+use crate::miller_reworked::MillerElementSet;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) struct EntryTimesBoth {
@@ -13,18 +15,54 @@ pub(crate) struct EntryTimesBoth {
     pub(crate) second_len: Option<u16>,
 }
 
+/// Both-edge record of a single frame, decoded with runtime [`DecoderConfig`].
 #[derive(Debug, Eq, PartialEq)]
-pub struct SetTimesBoth<const TICK_LEN: u16> {
+pub struct TimesBoth {
     time_both_set: Vec<EntryTimesBoth>,
+    config: DecoderConfig,
+    offset: usize,
+    chunk: usize,
+    start: u64,
 }
 
-impl<const TICK_LEN: u16> SetTimesBoth<TICK_LEN> {
+impl TimesBoth {
     /// Assume here that long intervals are `on` (no modulation).
-    pub fn from_raw(time_both_input: &[u16]) -> Vec<Self> {
+    pub fn from_raw(time_both_input: &[u16], config: DecoderConfig) -> Vec<Self> {
+        Self::from_raw_split(time_both_input, config, |interval| {
+            config.is_idle_both(interval)
+        })
+    }
+
+    /// Same as [`TimesBoth::from_raw`], for collision-aware decoding: frames
+    /// are separated by longer idle gaps, so that runs of collided bits are
+    /// kept within the frame.
+    pub fn from_raw_collisions(time_both_input: &[u16], config: DecoderConfig) -> Vec<Self> {
+        Self::from_raw_split(time_both_input, config, |interval| {
+            config.is_idle_collision(interval)
+        })
+    }
+
+    fn from_raw_split<F>(time_both_input: &[u16], config: DecoderConfig, is_idle: F) -> Vec<Self>
+    where
+        F: Fn(u16) -> bool,
+    {
+        let mut offset = 0;
+        let mut time = 0;
         time_both_input
-            .split(|interval| *interval > 15 * TICK_LEN)
-            .filter(|interval| interval.len() % 2 == 1)
-            .map(|slice| {
+            .split(|interval| is_idle(*interval))
+            .enumerate()
+            .map(|(chunk, slice)| {
+                let slice_offset = offset;
+                let slice_start = time;
+                // chunk and the idle gap after it
+                offset += slice.len() + 1;
+                time = time_both_input[slice_offset..offset.min(time_both_input.len())]
+                    .iter()
+                    .fold(time, |time, interval| time + *interval as u64);
+                (chunk, slice_offset, slice_start, slice)
+            })
+            .filter(|(_, _, _, slice)| slice.len() % 2 == 1)
+            .map(|(chunk, offset, start, slice)| {
                 let mut time_both_set: Vec<EntryTimesBoth> = Vec::new();
                 let slice_len = slice.len();
                 for i in 0..slice_len / 2 {
@@ -37,26 +75,215 @@ impl<const TICK_LEN: u16> SetTimesBoth<TICK_LEN> {
                     first_len: slice[slice_len - 1],
                     second_len: None,
                 });
-                SetTimesBoth::<TICK_LEN> { time_both_set }
+                TimesBoth {
+                    time_both_set,
+                    config,
+                    offset,
+                    chunk,
+                    start,
+                }
             })
             .collect()
     }
 
-    pub fn convert_to_miller(&self) -> Result<MillerElementSet, MillerError> {
+    /// Time of the first edge of the record, in timer counts from the first
+    /// edge in the buffer.
+    ///
+    /// For Manchester frame, modulated first half of start of frame is
+    /// before this edge.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Time of the edge starting the last interval of the record, in timer
+    /// counts from the first edge in the buffer.
+    ///
+    /// For Miller frame this is the start of the last pause, same as
+    /// [`MillerTimesDown::end`](crate::miller_reworked::MillerTimesDown::end).
+    /// For Manchester frame this is the end of the last modulated half-bit,
+    /// where PICC to PCD frame delay time starts.
+    pub fn end(&self) -> u64 {
+        // only the last entry has no second interval
+        self.time_both_set
+            .iter()
+            .filter_map(|time_both| Some(time_both.first_len as u64 + time_both.second_len? as u64))
+            .fold(self.start, |time, len| time + len)
+    }
+
+    pub fn convert_to_miller(&self) -> Result<MillerElementSet, MillerDecodeError> {
         let mut miller_element_set = MillerElementSet::new();
-        for time_both in self.time_both_set.iter() {
-            miller_element_set.add_time_both_interval::<TICK_LEN>(*time_both)?;
+        for (i, time_both) in self.time_both_set.iter().enumerate() {
+            miller_element_set
+                .add_time_both_interval(*time_both, &self.config)
+                .map_err(|error| {
+                    // pause is checked alone, gap is checked together with pause
+                    let offset = match error {
+                        MillerError::UnexpectedMillerOffInterval(_) => self.offset + 2 * i,
+                        _ => self.offset + 2 * i + 1,
+                    };
+                    miller_element_set.decode_error(error, offset, self.chunk)
+                })?;
         }
         Ok(miller_element_set)
     }
 
-    pub fn convert_to_manchester(&self) -> Result<ManchesterElementSet, ManchesterError> {
+    pub fn convert_to_manchester(&self) -> Result<ManchesterElementSet, ManchesterDecodeError> {
         let mut manchester_element_set = ManchesterElementSet::new();
-        for time_both in self.time_both_set.iter() {
-            manchester_element_set.add_time_both_interval::<TICK_LEN>(*time_both)?;
+        for (i, time_both) in self.time_both_set.iter().enumerate() {
+            manchester_element_set
+                .add_time_both_interval(*time_both, &self.config)
+                .map_err(|error| {
+                    let offset = match error {
+                        ManchesterError::UnexpectedEvenInterval(_) => self.offset + 2 * i + 1,
+                        _ => self.offset + 2 * i,
+                    };
+                    manchester_element_set.decode_error(error, offset, self.chunk)
+                })?;
         }
         Ok(manchester_element_set)
     }
+
+    /// Manchester elements, with collided bits recognized.
+    ///
+    /// Each interval is split into half-bit periods. Modulated first half of
+    /// start of frame is merged into the idle gap before the record.
+    pub fn convert_to_manchester_collisions(
+        &self,
+    ) -> Result<ManchesterElementSet, ManchesterDecodeError> {
+        let mut half_bits: Vec<(bool, usize)> = Vec::new();
+        half_bits.push((true, self.offset));
+        for (i, time_both) in self.time_both_set.iter().enumerate() {
+            let offset = self.offset + 2 * i;
+            let Some(halves) = self.config.half_bits(time_both.first_len) else {
+                return Err(ManchesterElementSet::from_half_bits(&half_bits, self.chunk)
+                    .unwrap_or_default()
+                    .decode_error(
+                        ManchesterError::UnexpectedOddInterval(time_both.first_len),
+                        offset,
+                        self.chunk,
+                    ));
+            };
+            half_bits.extend((0..halves).map(|_| (false, offset)));
+            if let Some(second_len) = time_both.second_len {
+                let Some(halves) = self.config.half_bits(second_len) else {
+                    return Err(ManchesterElementSet::from_half_bits(&half_bits, self.chunk)
+                        .unwrap_or_default()
+                        .decode_error(
+                            ManchesterError::UnexpectedEvenInterval(second_len),
+                            offset + 1,
+                            self.chunk,
+                        ));
+                };
+                half_bits.extend((0..halves).map(|_| (true, offset + 1)));
+            }
+        }
+        ManchesterElementSet::from_half_bits(&half_bits, self.chunk)
+    }
+}
+
+impl Frame {
+    /// Manchester frames from both-edge record, with time of the first and
+    /// the last edge of each frame, see [`TimesBoth::start`] and
+    /// [`TimesBoth::end`].
+    ///
+    /// First and last chunks are skipped as the buffer could start or end
+    /// mid-frame. Chunk that could not be decoded is skipped and its error is
+    /// kept. `capture_start` is the time of the first edge in the buffer, in
+    /// timer counts; 0 gives times relative to the buffer start.
+    pub fn process_buffer_manchester_timed<P>(
+        buffer: &[u16],
+        config: &DecoderConfig,
+        capture_start: u64,
+        frame_filter: P,
+    ) -> ManchesterBufferTimedFrames
+    where
+        P: Fn(&Self) -> bool,
+    {
+        let mut buffer_frames = ManchesterBufferTimedFrames::default();
+        let last_chunk = buffer
+            .split(|interval| config.is_idle_both(*interval))
+            .count()
+            - 1;
+        for times_both in TimesBoth::from_raw(buffer, *config) {
+            if (times_both.chunk == 0) | (times_both.chunk == last_chunk) {
+                continue;
+            }
+            let start = capture_start + times_both.start();
+            let end = capture_start + times_both.end();
+            match times_both
+                .convert_to_manchester()
+                .and_then(|manchester_element_set| {
+                    manchester_element_set.collect_frame().map_err(|error| {
+                        manchester_element_set.decode_error(
+                            error,
+                            times_both.offset + 2 * times_both.time_both_set.len() - 1,
+                            times_both.chunk,
+                        )
+                    })
+                }) {
+                Ok(frame) => {
+                    if frame_filter(&frame) {
+                        buffer_frames.frames.push(TimedFrame { frame, start, end })
+                    }
+                }
+                Err(error) => buffer_frames.errors.push(error),
+            }
+        }
+        buffer_frames
+    }
+}
+
+/// Timed frames decoded from the buffer, and errors in chunks that were
+/// skipped.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct ManchesterBufferTimedFrames {
+    pub frames: Vec<TimedFrame>,
+    pub errors: Vec<ManchesterDecodeError>,
+}
+
+/// Both-edge record of a single frame, with tick length known at compile time.
+#[derive(Debug, Eq, PartialEq)]
+pub struct SetTimesBoth<const TICK_LEN: u16> {
+    times_both: TimesBoth,
+}
+
+impl<const TICK_LEN: u16> SetTimesBoth<TICK_LEN> {
+    /// Assume here that long intervals are `on` (no modulation).
+    pub fn from_raw(time_both_input: &[u16]) -> Vec<Self> {
+        TimesBoth::from_raw(time_both_input, DecoderConfig::new(TICK_LEN))
+            .into_iter()
+            .map(|times_both| Self { times_both })
+            .collect()
+    }
+
+    pub fn convert_to_miller(&self) -> Result<MillerElementSet, MillerDecodeError> {
+        self.times_both.convert_to_miller()
+    }
+
+    pub fn convert_to_manchester(&self) -> Result<ManchesterElementSet, ManchesterDecodeError> {
+        self.times_both.convert_to_manchester()
+    }
+
+    pub fn start(&self) -> u64 {
+        self.times_both.start()
+    }
+
+    pub fn end(&self) -> u64 {
+        self.times_both.end()
+    }
+
+    pub fn from_raw_collisions(time_both_input: &[u16]) -> Vec<Self> {
+        TimesBoth::from_raw_collisions(time_both_input, DecoderConfig::new(TICK_LEN))
+            .into_iter()
+            .map(|times_both| Self { times_both })
+            .collect()
+    }
+
+    pub fn convert_to_manchester_collisions(
+        &self,
+    ) -> Result<ManchesterElementSet, ManchesterDecodeError> {
+        self.times_both.convert_to_manchester_collisions()
+    }
 }
 
 #[cfg(feature = "std")]
@@ -86,6 +313,44 @@ mod tests {
         assert_eq!(frame_set, expected_frame_set);
     }
 
+    #[test]
+    fn times_both_timing() {
+        let times_set = [
+            25001, 82, 101, 75, 191, 80, 102, 75, 191, 79, 189, 80, 189, 80, 1734, 97, 82, 97, 80,
+            176, 94, 96, 82, 98, 167, 180, 179, 102, 81, 97, 81, 98, 81, 97, 81, 98, 81, 176, 178,
+            102, 82, 175, 179, 181, 178, 102, 80, 177, 93, 98, 80, 98, 167, 101, 82, 97, 82, 256,
+            28703,
+        ];
+        let times_both = TimesBoth::from_raw(&times_set, DecoderConfig::new(22));
+        assert_eq!(times_both.len(), 2);
+        assert_eq!(times_both[0].start(), 25001);
+        // start of the last pause
+        assert_eq!(times_both[0].end(), 26515 - 80);
+        assert_eq!(times_both[1].start(), 28249);
+        // end of the last modulated half-bit
+        assert_eq!(
+            times_both[1].end(),
+            times_set[..times_set.len() - 2]
+                .iter()
+                .map(|interval| *interval as u64)
+                .sum::<u64>()
+        );
+
+        let config = DecoderConfig::new(22);
+        let timed_frames = Frame::process_buffer_manchester_timed(&times_set, &config, 0, |_| true);
+        assert_eq!(
+            timed_frames.frames,
+            vec![TimedFrame {
+                frame: Frame::Standard(vec![0xA3]),
+                start: times_both[1].start(),
+                end: times_both[1].end(),
+            }]
+        );
+        // Miller frame is not decoded as Manchester
+        assert_eq!(timed_frames.errors.len(), 1);
+        assert_eq!(timed_frames.errors[0].position.chunk, 1);
+    }
+
     #[test]
     fn check_many_frames_test1() {
         let times_set = [
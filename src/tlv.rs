@@ -0,0 +1,352 @@
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::emv::tag_name;
+use crate::error::TlvError;
+
+/// BER-TLV tag, all tag bytes in big-endian order, e.g. `0x9F38`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Tag(pub u32);
+
+impl Tag {
+    /// Tag from the start of `data`, with the number of tag bytes.
+    pub fn from_bytes(data: &[u8]) -> Result<(Self, usize), TlvError> {
+        let first = *data.first().ok_or(TlvError::TruncatedTag)?;
+        let mut tag = first as u32;
+        let mut len = 1;
+        if first & 0x1F == 0x1F {
+            loop {
+                let byte = *data.get(len).ok_or(TlvError::TruncatedTag)?;
+                len += 1;
+                if len > 4 {
+                    return Err(TlvError::TagTooLong);
+                }
+                tag = (tag << 8) | byte as u32;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+        }
+        Ok((Tag(tag), len))
+    }
+
+    fn first_byte(&self) -> u8 {
+        let mut first = self.0;
+        while first > 0xFF {
+            first >>= 8;
+        }
+        first as u8
+    }
+
+    /// Value is a sequence of TLV objects.
+    pub fn is_constructed(&self) -> bool {
+        self.first_byte() & 0x20 != 0
+    }
+
+    /// Name from EMV tag dictionary.
+    pub fn name(&self) -> Option<&'static str> {
+        tag_name(*self)
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 > 0xFFFFFF {
+            write!(f, "{:08X}", self.0)
+        } else if self.0 > 0xFFFF {
+            write!(f, "{:06X}", self.0)
+        } else if self.0 > 0xFF {
+            write!(f, "{:04X}", self.0)
+        } else {
+            write!(f, "{:02X}", self.0)
+        }
+    }
+}
+
+/// Length from the start of `data`, with the number of length bytes.
+fn length_from_bytes(data: &[u8]) -> Result<(usize, usize), TlvError> {
+    let first = *data.first().ok_or(TlvError::TruncatedLength)?;
+    match first {
+        0x00..=0x7F => Ok((first as usize, 1)),
+        0x80 => Err(TlvError::IndefiniteLength),
+        0x81..=0x84 => {
+            let count = (first & 0x7F) as usize;
+            let bytes = data.get(1..1 + count).ok_or(TlvError::TruncatedLength)?;
+            let length = bytes
+                .iter()
+                .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+            Ok((length, 1 + count))
+        }
+        _ => Err(TlvError::LengthTooLong),
+    }
+}
+
+/// Deepest nesting of constructed data objects accepted by the parser.
+///
+/// EMV templates are nested a few levels deep; the limit keeps recursion
+/// bounded on crafted card data.
+pub const MAX_DEPTH: usize = 16;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TlvValue {
+    Primitive(Vec<u8>),
+    Constructed(Vec<Tlv>),
+}
+
+/// BER-TLV data object.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tlv {
+    pub tag: Tag,
+    pub value: TlvValue,
+}
+
+impl Tlv {
+    /// All data objects in `data`.
+    ///
+    /// Padding bytes 0x00 and 0xFF between data objects are skipped.
+    pub fn parse_all(data: &[u8]) -> Result<Vec<Self>, TlvError> {
+        Self::parse_all_nested(data, 0)
+    }
+
+    /// Single data object from the start of `data`, with its encoded length.
+    pub fn parse(data: &[u8]) -> Result<(Self, usize), TlvError> {
+        Self::parse_nested(data, 0)
+    }
+
+    fn parse_all_nested(data: &[u8], depth: usize) -> Result<Vec<Self>, TlvError> {
+        let mut tlvs = Vec::new();
+        let mut rest = data;
+        while let Some(first) = rest.first() {
+            if (*first == 0x00) | (*first == 0xFF) {
+                rest = &rest[1..];
+                continue;
+            }
+            let (tlv, len) = Self::parse_nested(rest, depth)?;
+            tlvs.push(tlv);
+            rest = &rest[len..];
+        }
+        Ok(tlvs)
+    }
+
+    fn parse_nested(data: &[u8], depth: usize) -> Result<(Self, usize), TlvError> {
+        let (tag, tag_len) = Tag::from_bytes(data)?;
+        let (length, length_len) = length_from_bytes(&data[tag_len..])?;
+        let start = tag_len + length_len;
+        let end = start.checked_add(length).ok_or(TlvError::TruncatedValue)?;
+        let value = data.get(start..end).ok_or(TlvError::TruncatedValue)?;
+        let value = if tag.is_constructed() {
+            if depth >= MAX_DEPTH {
+                return Err(TlvError::TooDeep);
+            }
+            TlvValue::Constructed(Self::parse_all_nested(value, depth + 1)?)
+        } else {
+            TlvValue::Primitive(value.to_vec())
+        };
+        Ok((Tlv { tag, value }, end))
+    }
+
+    /// First data object with given tag, this one or nested, depth first.
+    pub fn find(&self, tag: Tag) -> Option<&Tlv> {
+        if self.tag == tag {
+            return Some(self);
+        }
+        match &self.value {
+            TlvValue::Constructed(tlvs) => find(tlvs, tag),
+            TlvValue::Primitive(_) => None,
+        }
+    }
+
+    /// Value of primitive data object.
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match &self.value {
+            TlvValue::Primitive(value) => Some(value),
+            TlvValue::Constructed(_) => None,
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        write!(f, "{:width$}{}", "", self.tag, width = depth * 2)?;
+        if let Some(name) = self.tag.name() {
+            write!(f, " {name}")?;
+        }
+        match &self.value {
+            TlvValue::Primitive(value) => {
+                write!(f, ": ")?;
+                for byte in value.iter() {
+                    write!(f, "{byte:02X}")?;
+                }
+                writeln!(f)
+            }
+            TlvValue::Constructed(tlvs) => {
+                writeln!(f)?;
+                for tlv in tlvs.iter() {
+                    tlv.fmt_indented(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Tree of data objects, one per line, nested objects indented.
+impl fmt::Display for Tlv {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// First data object with given tag in `tlvs`, nested ones included.
+pub fn find(tlvs: &[Tlv], tag: Tag) -> Option<&Tlv> {
+    tlvs.iter().find_map(|tlv| tlv.find(tag))
+}
+
+/// Data object list (e.g. PDOL or CDOL1), tags with lengths and no values.
+pub fn parse_dol(data: &[u8]) -> Result<Vec<(Tag, usize)>, TlvError> {
+    let mut dol = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let (tag, tag_len) = Tag::from_bytes(rest)?;
+        let (length, length_len) = length_from_bytes(&rest[tag_len..])?;
+        dol.push((tag, length));
+        rest = &rest[tag_len + length_len..];
+    }
+    Ok(dol)
+}
+
+/// Split data sent for data object list into values of listed tags.
+///
+/// `None` if data length does not match the list.
+pub fn split_dol_data<'a>(dol: &[(Tag, usize)], data: &'a [u8]) -> Option<Vec<(Tag, &'a [u8])>> {
+    let mut values = Vec::with_capacity(dol.len());
+    let mut rest = data;
+    for (tag, length) in dol.iter() {
+        if rest.len() < *length {
+            return None;
+        }
+        let (value, after) = rest.split_at(*length);
+        values.push((*tag, value));
+        rest = after;
+    }
+    rest.is_empty().then_some(values)
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    const PPSE_RESPONSE: [u8; 47] = [
+        0x6F, 0x2D, 0x84, 0x0E, 0x32, 0x50, 0x41, 0x59, 0x2E, 0x53, 0x59, 0x53, 0x2E, 0x44, 0x44,
+        0x46, 0x30, 0x31, 0xA5, 0x1B, 0xBF, 0x0C, 0x18, 0x61, 0x16, 0x4F, 0x07, 0xA0, 0x00, 0x00,
+        0x00, 0x04, 0x10, 0x10, 0x50, 0x08, 0x4D, 0x41, 0x53, 0x54, 0x45, 0x52, 0x43, 0x41, 0x87,
+        0x01, 0x01,
+    ];
+
+    #[test]
+    fn ppse_tree() {
+        let tlvs = Tlv::parse_all(&PPSE_RESPONSE).unwrap();
+        assert_eq!(tlvs.len(), 1);
+        assert_eq!(
+            find(&tlvs, Tag(0x4F)).unwrap().bytes(),
+            Some([0xA0, 0x00, 0x00, 0x00, 0x04, 0x10, 0x10].as_slice())
+        );
+        assert!(Tag(0xBF0C).is_constructed());
+        assert_eq!(
+            tlvs[0].to_string(),
+            "6F File Control Information (FCI) Template
+  84 Dedicated File (DF) Name: 325041592E5359532E4444463031
+  A5 File Control Information (FCI) Proprietary Template
+    BF0C File Control Information (FCI) Issuer Discretionary Data
+      61 Application Template
+        4F Application Identifier (AID): A0000000041010
+        50 Application Label: 4D41535445524341
+        87 Application Priority Indicator: 01
+"
+        );
+    }
+
+    #[test]
+    fn long_length_and_padding() {
+        let mut data = vec![0x00, 0x5F, 0x20, 0x81, 0x80];
+        data.extend([0x41; 0x80]);
+        data.push(0xFF);
+        let tlvs = Tlv::parse_all(&data).unwrap();
+        assert_eq!(tlvs.len(), 1);
+        assert_eq!(tlvs[0].tag, Tag(0x5F20));
+        assert_eq!(tlvs[0].bytes().unwrap().len(), 0x80);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(Tlv::parse_all(&[0x9F]), Err(TlvError::TruncatedTag));
+        assert_eq!(
+            Tlv::parse_all(&[0x9F, 0x81, 0x82, 0x83, 0x04, 0x00]),
+            Err(TlvError::TagTooLong)
+        );
+        assert_eq!(Tlv::parse_all(&[0x5A]), Err(TlvError::TruncatedLength));
+        assert_eq!(
+            Tlv::parse_all(&[0x5A, 0x82, 0x01]),
+            Err(TlvError::TruncatedLength)
+        );
+        assert_eq!(
+            Tlv::parse_all(&[0x5A, 0x80]),
+            Err(TlvError::IndefiniteLength)
+        );
+        assert_eq!(Tlv::parse_all(&[0x5A, 0x85]), Err(TlvError::LengthTooLong));
+        assert_eq!(
+            Tlv::parse_all(&[0x5A, 0x03, 0x01]),
+            Err(TlvError::TruncatedValue)
+        );
+        // nested object longer than its template
+        assert_eq!(
+            Tlv::parse_all(&[0x70, 0x03, 0x5A, 0x04, 0x01]),
+            Err(TlvError::TruncatedValue)
+        );
+        assert_eq!(
+            Tlv::parse_all(&[0x5A, 0x84, 0xFF, 0xFF, 0xFF, 0xFF]),
+            Err(TlvError::TruncatedValue)
+        );
+    }
+
+    /// Templates 0x70 nested `depth` levels, innermost one empty.
+    fn nested(depth: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        for _ in 0..depth {
+            let mut template = vec![0x70, 0x82];
+            template.extend((data.len() as u16).to_be_bytes());
+            template.extend(data);
+            data = template;
+        }
+        data
+    }
+
+    #[test]
+    fn depth_limit() {
+        assert!(Tlv::parse_all(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            Tlv::parse_all(&nested(MAX_DEPTH + 1)),
+            Err(TlvError::TooDeep)
+        );
+        assert_eq!(Tlv::parse_all(&nested(10_000)), Err(TlvError::TooDeep));
+    }
+
+    #[test]
+    fn dol() {
+        let pdol = parse_dol(&[0x9F, 0x66, 0x04, 0x9F, 0x02, 0x06, 0x5F, 0x2A, 0x02]).unwrap();
+        assert_eq!(
+            pdol,
+            vec![(Tag(0x9F66), 4), (Tag(0x9F02), 6), (Tag(0x5F2A), 2)]
+        );
+        let data = [
+            0x36, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x09, 0x78,
+        ];
+        let values = split_dol_data(&pdol, &data).unwrap();
+        assert_eq!(values[2], (Tag(0x5F2A), [0x09, 0x78].as_slice()));
+        assert_eq!(split_dol_data(&pdol, &data[1..]), None);
+    }
+}